@@ -1,4 +1,11 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::encoding::OutputEncoding;
+use crate::machine::{ReflectorMode, RotorMode};
+use crate::position::PositionFormat;
+use crate::state::Endian;
 
 #[derive(Parser)]
 #[command(name = "rotorix-cli")]
@@ -15,6 +22,15 @@ pub enum Command {
 
     /// Decrypt a string
     Decrypt(CommandOptions),
+
+    /// Show the substitution alphabet at the initial state
+    ShowAlphabet(ShowAlphabetOptions),
+
+    /// Generate one key sheet per day for a date range
+    Keygen(KeygenOptions),
+
+    /// Process lines interactively, keeping the state between lines
+    Repl(ReplOptions),
 }
 
 #[derive(Parser)]
@@ -22,39 +38,142 @@ pub struct CommandOptions {
     /// Input string
     pub input: String,
 
+    #[command(flatten)]
+    pub machine: MachineOptions,
+
+    /// Verbose output
+    #[arg(long)]
+    pub verbose: bool,
+
+    /// Trace each byte through the Enigma pipeline
+    #[arg(long)]
+    pub trace: bool,
+
+    /// Rotor position display
+    #[arg(long, value_enum, default_value_t = PositionFormat::Numbers)]
+    pub position_format: PositionFormat,
+
+    /// Output encoding
+    #[arg(long, value_enum, default_value_t = OutputEncoding::Base32)]
+    pub encoding: OutputEncoding,
+
+    /// Split the encoded output into space-separated groups of N characters
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub group: Option<u32>,
+
+    /// Prefix the encoded output with TAG: (checked on decrypt)
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// Mix a random per-message nonce into the start positions and
+    /// carry it in clear ahead of the ciphertext
+    #[arg(long)]
+    pub nonce: bool,
+
+    /// Pad the plaintext with random bytes to a multiple of N bytes
+    /// (the same value must be passed to decrypt)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub pad: Option<u32>,
+}
+
+#[derive(Parser)]
+pub struct ShowAlphabetOptions {
+    #[command(flatten)]
+    pub machine: MachineOptions,
+
+    /// Byte range to show (format: FROM-TO as byte values)
+    #[arg(long, default_value = "32-126")]
+    pub range: String,
+}
+
+#[derive(Parser)]
+pub struct ReplOptions {
+    #[command(flatten)]
+    pub machine: MachineOptions,
+
+    /// Rotor position display
+    #[arg(long, value_enum, default_value_t = PositionFormat::Numbers)]
+    pub position_format: PositionFormat,
+
+    /// Ciphertext encoding
+    #[arg(long, value_enum, default_value_t = OutputEncoding::Base32)]
+    pub encoding: OutputEncoding,
+}
+
+#[derive(Parser)]
+pub struct KeygenOptions {
+    /// First day (format: YYYYMMDD)
+    #[arg(long)]
+    pub from: String,
+
+    /// Last day, inclusive (format: YYYYMMDD)
+    #[arg(long)]
+    pub to: String,
+
+    /// Master seed the daily keys are derived from
+    #[arg(
+        long,
+        conflicts_with = "seed_file",
+        required_unless_present = "seed_file"
+    )]
+    pub seed: Option<u64>,
+
+    /// Read the master seed from a file (decimal or 0x-prefixed hex)
+    #[arg(long)]
+    pub seed_file: Option<PathBuf>,
+
+    /// Number of rotors in each daily machine
+    #[arg(long, default_value_t = 3)]
+    pub rotors: usize,
+}
+
+/// Options describing the machine and its initial state.
+#[derive(Args)]
+pub struct MachineOptions {
     /// Number of rotors
     #[arg(long, default_value_t = 1)]
     pub rotors: usize,
 
-    /// Rotor mode: identity | shifted | seed
-    #[arg(long, default_value = "identity")]
-    pub rotor_mode: String,
+    /// Rotor mode
+    #[arg(long, value_enum, default_value_t = RotorMode::Identity)]
+    pub rotor_mode: RotorMode,
 
-    /// Reflector mode: identity or paired
-    #[arg(long, default_value = "identity")]
-    pub reflector_mode: String,
+    /// Reflector mode
+    #[arg(long, value_enum, default_value_t = ReflectorMode::Identity)]
+    pub reflector_mode: ReflectorMode,
 
     /// Stepping modulus
     #[arg(long, default_value_t = 256)]
     pub steps: u32,
 
     /// Seed for deterministic initial rotor positions
-    #[arg(long)]
+    #[arg(long, conflicts_with = "seed_file")]
     pub seed: Option<u64>,
 
-    /// Simple plugboard swap (format: A:B as byte values)
+    /// Read the seed from a file (decimal or 0x-prefixed hex)
     #[arg(long)]
-    pub swap: Option<String>,
+    pub seed_file: Option<PathBuf>,
 
-    /// Verbose output
-    #[arg(long)]
-    pub verbose: bool,
+    /// Comma-separated wiring seed per rotor (seed mode); overrides
+    /// --seed for the rotor wiring
+    #[arg(long, value_delimiter = ',')]
+    pub rotor_seeds: Option<Vec<u64>>,
 
-    /// Trace each byte through the Enigma pipeline
+    /// Byte order used to derive rotor positions from the seed
+    #[arg(long, value_enum, default_value_t = Endian::Little)]
+    pub endian: Endian,
+
+    /// Number of steps to skip before processing (warm-up)
+    #[arg(long, default_value_t = 0)]
+    pub skip: u64,
+
+    /// Simple plugboard swap (format: A:B as byte values or single
+    /// ASCII characters).
+    /// Omitting it leaves the plugboard as identity.
     #[arg(long)]
-    pub trace: bool,
+    pub swap: Option<String>,
 
-    /// Output encoding: base32, hex, or base64
-    #[arg(long, default_value = "base32")]
-    pub encoding: String,
+    /// Explicitly use no plugboard (identity)
+    #[arg(long, conflicts_with = "swap")]
+    pub no_plugboard: bool,
 }
@@ -1,26 +1,150 @@
-use data_encoding::BASE64_NOPAD;
-use data_encoding::{BASE32HEX_NOPAD, HEXUPPER};
-
-pub fn encode_ciphertext(bytes: &[u8], encoding: &str) -> String {
-    match encoding {
-        "hex" => HEXUPPER.encode(bytes),
-        "base64" => BASE64_NOPAD.encode(bytes),
-        "base32" => BASE32HEX_NOPAD.encode(bytes),
-        _ => panic!("unsupported encoding: {}", encoding),
-    }
-}
-
-pub fn decode_ciphertext(s: &str, encoding: &str) -> Vec<u8> {
-    match encoding {
-        "hex" => HEXUPPER
-            .decode(s.as_bytes())
-            .expect("invalid HEX ciphertext"),
-        "base64" => BASE64_NOPAD
-            .decode(s.as_bytes())
-            .expect("invalid Base64 ciphertext"),
-        "base32" => BASE32HEX_NOPAD
-            .decode(s.as_bytes())
-            .expect("invalid Base32 ciphertext"),
-        _ => panic!("unsupported encoding: {}", encoding),
+use clap::ValueEnum;
+use data_encoding::{BASE32HEX_NOPAD, BASE64_NOPAD, Encoding, HEXUPPER};
+
+/// Text encoding for ciphertext on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputEncoding {
+    /// Base32 with the extended hex alphabet
+    Base32,
+    /// Uppercase hexadecimal
+    Hex,
+    /// Base64 without padding
+    Base64,
+}
+
+/// Formats ciphertext bytes for output and parses them back.
+///
+/// Encodings implement this trait directly; decorators such as
+/// [`Grouped`] and [`Tagged`] wrap another formatter.
+pub trait OutputFormatter {
+    /// Format bytes as text.
+    fn format(&self, bytes: &[u8]) -> String;
+
+    /// Parse text produced by [`OutputFormatter::format`].
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String>;
+}
+
+/// Uppercase hexadecimal.
+pub struct Hex;
+
+/// Base32 (extended hex alphabet, no padding).
+pub struct Base32;
+
+/// Base64 (no padding).
+pub struct Base64;
+
+/// Decode with a `data-encoding` codec, ignoring any embedded ASCII
+/// whitespace (e.g. line breaks from wrapped or pasted text).
+fn decode(encoding: &Encoding, name: &str, s: &str) -> Result<Vec<u8>, String> {
+    let s: String = s.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+
+    encoding
+        .decode(s.as_bytes())
+        .map_err(|e| format!("invalid {name} ciphertext: {e}"))
+}
+
+impl OutputFormatter for Hex {
+    fn format(&self, bytes: &[u8]) -> String {
+        HEXUPPER.encode(bytes)
+    }
+
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String> {
+        decode(&HEXUPPER, "HEX", s)
+    }
+}
+
+impl OutputFormatter for Base32 {
+    fn format(&self, bytes: &[u8]) -> String {
+        BASE32HEX_NOPAD.encode(bytes)
+    }
+
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String> {
+        decode(&BASE32HEX_NOPAD, "Base32", s)
+    }
+}
+
+impl OutputFormatter for Base64 {
+    fn format(&self, bytes: &[u8]) -> String {
+        BASE64_NOPAD.encode(bytes)
+    }
+
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String> {
+        decode(&BASE64_NOPAD, "Base64", s)
     }
 }
+
+/// Splits the inner output into space-separated groups.
+pub struct Grouped {
+    pub inner: Box<dyn OutputFormatter>,
+    pub size: usize,
+}
+
+impl OutputFormatter for Grouped {
+    fn format(&self, bytes: &[u8]) -> String {
+        let text: Vec<char> = self.inner.format(bytes).chars().collect();
+
+        text.chunks(self.size)
+            .map(|c| c.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String> {
+        let s: String = s.split_whitespace().collect();
+        self.inner.parse(&s)
+    }
+}
+
+/// Prefixes the inner output with `TAG:`.
+pub struct Tagged {
+    pub inner: Box<dyn OutputFormatter>,
+    pub tag: String,
+}
+
+impl OutputFormatter for Tagged {
+    fn format(&self, bytes: &[u8]) -> String {
+        format!("{}:{}", self.tag, self.inner.format(bytes))
+    }
+
+    fn parse(&self, s: &str) -> Result<Vec<u8>, String> {
+        let body = s
+            .trim_start()
+            .strip_prefix(&self.tag)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| format!("ciphertext is not tagged with '{}'", self.tag))?;
+
+        self.inner.parse(body)
+    }
+}
+
+/// Compose the formatter selected by the command-line flags.
+///
+/// Grouping is applied to the encoded text and tagging wraps the
+/// grouped result.
+pub fn build_formatter(
+    encoding: OutputEncoding,
+    group: Option<usize>,
+    tag: Option<String>,
+) -> Box<dyn OutputFormatter> {
+    let mut formatter: Box<dyn OutputFormatter> = match encoding {
+        OutputEncoding::Hex => Box::new(Hex),
+        OutputEncoding::Base64 => Box::new(Base64),
+        OutputEncoding::Base32 => Box::new(Base32),
+    };
+
+    if let Some(size) = group {
+        formatter = Box::new(Grouped {
+            inner: formatter,
+            size,
+        });
+    }
+
+    if let Some(tag) = tag {
+        formatter = Box::new(Tagged {
+            inner: formatter,
+            tag,
+        });
+    }
+
+    formatter
+}
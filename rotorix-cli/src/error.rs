@@ -0,0 +1,63 @@
+use std::fmt;
+use std::io;
+
+use rotorix_core::EnigmaError;
+
+/// Errors reported by CLI commands.
+///
+/// Each class maps to its own exit code so scripts can tell failures
+/// apart. Exit code 2 is left to clap for usage errors.
+#[derive(Debug)]
+pub enum CliError {
+    /// Reading input or writing output failed.
+    Io(String),
+
+    /// Ciphertext could not be decoded.
+    Decode(String),
+
+    /// Command-line options are invalid or inconsistent.
+    Config(String),
+
+    /// The core library rejected the machine or its state.
+    Machine(EnigmaError),
+}
+
+/// Result type returned by CLI commands.
+pub type CliResult<T> = Result<T, CliError>;
+
+impl CliError {
+    /// Process exit code for this error class.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Decode(_) => 3,
+            CliError::Config(_) => 4,
+            CliError::Machine(_) => 5,
+            CliError::Io(_) => 6,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Io(msg) => write!(f, "I/O error: {msg}"),
+            CliError::Decode(msg) => write!(f, "decode error: {msg}"),
+            CliError::Config(msg) => write!(f, "configuration error: {msg}"),
+            CliError::Machine(err) => write!(f, "machine error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Io(err.to_string())
+    }
+}
+
+impl From<EnigmaError> for CliError {
+    fn from(err: EnigmaError) -> Self {
+        CliError::Machine(err)
+    }
+}
@@ -0,0 +1,99 @@
+use rotorix_core::prng::mix64;
+
+/// Maximum number of days in one keygen run.
+pub const MAX_DAYS: usize = 366;
+
+/// A calendar date parsed from `YYYYMMDD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Parse and validate a `YYYYMMDD` date.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s.len() != 8 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(format!("invalid date '{s}': expected YYYYMMDD"));
+        }
+
+        let date = Self {
+            year: s[..4].parse().unwrap(),
+            month: s[4..6].parse().unwrap(),
+            day: s[6..].parse().unwrap(),
+        };
+
+        if !(1..=12).contains(&date.month) || date.day == 0 || date.day > date.days_in_month() {
+            return Err(format!("invalid date '{s}': no such day"));
+        }
+
+        Ok(date)
+    }
+
+    fn days_in_month(&self) -> u32 {
+        match self.month {
+            4 | 6 | 9 | 11 => 30,
+            2 if self.is_leap_year() => 29,
+            2 => 28,
+            _ => 31,
+        }
+    }
+
+    fn is_leap_year(&self) -> bool {
+        (self.year.is_multiple_of(4) && !self.year.is_multiple_of(100))
+            || self.year.is_multiple_of(400)
+    }
+
+    /// Return the following calendar day.
+    pub fn next(self) -> Self {
+        if self.day < self.days_in_month() {
+            Self {
+                day: self.day + 1,
+                ..self
+            }
+        } else if self.month < 12 {
+            Self {
+                month: self.month + 1,
+                day: 1,
+                ..self
+            }
+        } else {
+            Self {
+                year: self.year + 1,
+                month: 1,
+                day: 1,
+            }
+        }
+    }
+
+    /// Date as the number `YYYYMMDD`.
+    pub fn as_number(&self) -> u64 {
+        u64::from(self.year * 10_000 + self.month * 100 + self.day)
+    }
+}
+
+/// List all dates from `from` to `to` inclusive.
+pub fn date_range(from: Date, to: Date) -> Result<Vec<Date>, String> {
+    if from > to {
+        return Err("--from must not be after --to".into());
+    }
+
+    let mut dates = vec![from];
+    while *dates.last().unwrap() < to {
+        if dates.len() == MAX_DAYS {
+            return Err(format!("date range exceeds {MAX_DAYS} days"));
+        }
+        dates.push(dates.last().unwrap().next());
+    }
+
+    Ok(dates)
+}
+
+/// Derive the daily seed from the master seed and the date.
+///
+/// Uses the SplitMix64 finalizer so consecutive dates yield
+/// unrelated seeds.
+pub fn day_seed(master: u64, date: Date) -> u64 {
+    mix64(master ^ date.as_number().wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
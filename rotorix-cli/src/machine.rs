@@ -1,42 +1,85 @@
-use rotorix_core::{EnigmaComponent, EnigmaMachine, LinearStepping, Reflector, Rotor};
+use clap::ValueEnum;
+use rotorix_core::{EnigmaComponent, EnigmaMachine, LinearStepping, Plugboard, Reflector, Rotor};
 
-use crate::plugboard::build_plugboard;
+use crate::error::{CliError, CliResult};
 
+/// How the rotors are wired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RotorMode {
+    /// Rotors that pass every byte through
+    Identity,
+    /// Rotors shifting every byte by 13
+    Shifted,
+    /// Pseudo-random wiring derived from a seed
+    Seed,
+}
+
+/// How the reflector is wired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReflectorMode {
+    /// Reflector that maps every byte to itself
+    Identity,
+    /// Reflector pairing 0 <-> 1, 2 <-> 3, ...
+    Paired,
+}
+
+/// Build the machine described by the command-line options.
+///
+/// In seed mode each rotor is wired from `rotor_seeds[i]` when given,
+/// otherwise from the shared `seed` and the rotor index.
 pub fn build_machine(
     rotor_count: usize,
     step_modulus: u32,
-    swap: Option<String>,
-    rotor_mode: String,
-    reflector_mode: String,
+    plugboard: Plugboard,
+    rotor_mode: RotorMode,
+    reflector_mode: ReflectorMode,
     seed: Option<u64>,
-) -> EnigmaMachine {
-    let plugboard = Box::new(build_plugboard(swap));
+    rotor_seeds: Option<&[u64]>,
+) -> CliResult<EnigmaMachine> {
+    if let Some(seeds) = rotor_seeds {
+        if rotor_mode != RotorMode::Seed {
+            return Err(CliError::Config(
+                "--rotor-seeds requires --rotor-mode seed".into(),
+            ));
+        }
+        if seeds.len() != rotor_count {
+            return Err(CliError::Config(format!(
+                "--rotor-seeds has {} seed(s) but --rotors is {rotor_count}",
+                seeds.len()
+            )));
+        }
+    }
 
     let mut rotors: Vec<Box<dyn EnigmaComponent>> = Vec::new();
     for i in 0..rotor_count {
-        match rotor_mode.as_str() {
-            "identity" => {
+        match rotor_mode {
+            RotorMode::Identity => {
                 rotors.push(Box::new(Rotor::identity(i)));
             }
-            "shifted" => {
+            RotorMode::Shifted => {
                 rotors.push(Box::new(Rotor::shifted(i, 13)));
             }
-            "seed" => {
-                let seed = seed.expect("seed-based rotor requires --seed");
+            RotorMode::Seed => {
+                let seed = rotor_seeds
+                    .map(|seeds| seeds[i])
+                    .or(seed)
+                    .ok_or_else(|| CliError::Config("seed-based rotor requires --seed".into()))?;
                 rotors.push(Box::new(Rotor::from_seed(i, seed)));
             }
-            _ => panic!("unknown rotor mode"),
         }
     }
 
-    let reflector = match reflector_mode.as_str() {
-        "identity" => Box::new(Reflector::identity()),
-        "paired" => Box::new(Reflector::paired()),
-        _ => panic!("unknown reflector mode"),
+    let reflector = match reflector_mode {
+        ReflectorMode::Identity => Box::new(Reflector::identity()),
+        ReflectorMode::Paired => Box::new(Reflector::paired()),
     };
 
-    let stepping = Box::new(LinearStepping::new(step_modulus));
+    let stepping = Box::new(LinearStepping::try_new(step_modulus)?);
 
-    EnigmaMachine::new(plugboard, rotors, reflector, stepping)
-        .expect("invalid Enigma configuration")
+    Ok(EnigmaMachine::new(
+        Box::new(plugboard),
+        rotors,
+        reflector,
+        stepping,
+    )?)
 }
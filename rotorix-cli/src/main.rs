@@ -1,40 +1,91 @@
 mod cli;
 mod encoding;
+mod error;
+mod keygen;
 mod machine;
+mod padding;
 mod plugboard;
+mod position;
+mod random;
+mod repl;
+mod seed;
+mod state;
+
+use std::process::ExitCode;
 
 use clap::Parser;
-use rotorix_core::EnigmaState;
+use rotorix_core::{EnigmaMachine, EnigmaState};
 
-use crate::encoding::{decode_ciphertext, encode_ciphertext};
-use cli::{Cli, Command, CommandOptions};
+use crate::encoding::build_formatter;
+use crate::error::{CliError, CliResult};
+use cli::{
+    Cli, Command, CommandOptions, KeygenOptions, MachineOptions, ReplOptions, ShowAlphabetOptions,
+};
+use keygen::{Date, date_range, day_seed};
 use machine::build_machine;
+use padding::{pad, unpad};
+use plugboard::{build_plugboard, describe_plugboard};
+use position::format_state;
+use random::random_bytes;
+use repl::run_repl;
+use seed::resolve_seed;
+use state::{build_state, mix_nonce};
 
-/// Build initial Enigma state, optionally seeded.
-fn build_state(rotors: usize, seed: Option<u64>) -> EnigmaState {
-    let mut state = EnigmaState::new(rotors);
+/// Rotor position cycles shorter than this many steps trigger a
+/// warning, since the keystream repeats within a short message.
+///
+/// Kept at the period of the default machine (one rotor, 256 steps)
+/// so that only configurations weaker than the default are flagged.
+const WEAK_PERIOD_THRESHOLD: u64 = 256;
 
-    if let Some(seed) = seed {
-        for (i, pos) in state.rotor_positions.iter_mut().enumerate() {
-            *pos = ((seed >> (i * 8)) & 0xFF) as u32;
-        }
-    }
+/// Build the machine and its initial state from the command line.
+///
+/// In `verbose` mode the effective plugboard is reported on stderr.
+fn setup(opts: &MachineOptions, verbose: bool) -> CliResult<(EnigmaMachine, EnigmaState)> {
+    let seed = resolve_seed(opts.seed, opts.seed_file.as_deref())?;
 
-    state
-}
+    let plugboard = build_plugboard(opts.swap.as_deref(), opts.no_plugboard)?;
+    if verbose {
+        eprintln!("plugboard: {}", describe_plugboard(&plugboard));
+    }
 
-fn run_encrypt(opts: CommandOptions) {
     let machine = build_machine(
         opts.rotors,
         opts.steps,
-        opts.swap.clone(),
-        opts.rotor_mode.clone(),
-        opts.reflector_mode.clone(),
-        opts.seed,
-    );
+        plugboard,
+        opts.rotor_mode,
+        opts.reflector_mode,
+        seed,
+        opts.rotor_seeds.as_deref(),
+    )?;
+
+    let mut state = build_state(opts.rotors, seed, opts.endian, opts.steps);
+    machine.skip(&mut state, opts.skip)?;
+
+    if machine.is_weak_period(&state, WEAK_PERIOD_THRESHOLD)? {
+        eprintln!(
+            "warning: rotor positions repeat within {WEAK_PERIOD_THRESHOLD} steps; \
+             consider more rotors or a larger --steps"
+        );
+    }
+
+    Ok((machine, state))
+}
+
+fn run_encrypt(opts: CommandOptions) -> CliResult<()> {
+    let (machine, mut state) = setup(&opts.machine, opts.verbose)?;
+
+    let nonce = if opts.nonce {
+        random_bytes(opts.machine.rotors)?
+    } else {
+        Vec::new()
+    };
+    mix_nonce(&mut state, &nonce, opts.machine.steps);
 
-    let mut state = build_state(opts.rotors, opts.seed);
-    let input = opts.input.as_bytes();
+    let input = match opts.pad {
+        Some(block) => pad(opts.input.as_bytes(), block as usize)?,
+        None => opts.input.as_bytes().to_vec(),
+    };
 
     let mut ciphertext = Vec::with_capacity(input.len());
 
@@ -42,44 +93,54 @@ fn run_encrypt(opts: CommandOptions) {
         for (i, &b) in input.iter().enumerate() {
             println!("[{}] '{}' ({})", i, b as char, b);
             println!(
-                "  state before: pos={:?}, step={}",
-                state.rotor_positions, state.step_counter
+                "  state before: {}",
+                format_state(&state, opts.position_format)
             );
 
-            let out = machine
-                .process_byte(b, &mut state)
-                .expect("encryption failed");
+            let out = machine.process_byte(b, &mut state)?;
 
             println!("  output byte: {}", out);
             println!(
-                "  state after:  pos={:?}, step={}",
-                state.rotor_positions, state.step_counter
+                "  state after:  {}",
+                format_state(&state, opts.position_format)
             );
             println!();
 
             ciphertext.push(out);
         }
     } else {
-        ciphertext = machine
-            .process_bytes(input, &mut state)
-            .expect("encryption failed");
+        ciphertext = machine.process_bytes(&input, &mut state)?;
     }
 
-    println!("{}", encode_ciphertext(&ciphertext, &opts.encoding));
+    let output = [nonce, ciphertext].concat();
+    let formatter = build_formatter(
+        opts.encoding,
+        opts.group.map(|g| g as usize),
+        opts.tag.clone(),
+    );
+    println!("{}", formatter.format(&output));
+
+    Ok(())
 }
 
-fn run_decrypt(opts: CommandOptions) {
-    let machine = build_machine(
-        opts.rotors,
-        opts.steps,
-        opts.swap.clone(),
-        opts.rotor_mode.clone(),
-        opts.reflector_mode.clone(),
-        opts.seed,
+fn run_decrypt(opts: CommandOptions) -> CliResult<()> {
+    let (machine, mut state) = setup(&opts.machine, opts.verbose)?;
+    let formatter = build_formatter(
+        opts.encoding,
+        opts.group.map(|g| g as usize),
+        opts.tag.clone(),
     );
+    let mut ciphertext = formatter.parse(&opts.input).map_err(CliError::Decode)?;
 
-    let mut state = build_state(opts.rotors, opts.seed);
-    let ciphertext = decode_ciphertext(&opts.input, &opts.encoding);
+    if opts.nonce {
+        if ciphertext.len() < opts.machine.rotors {
+            return Err(CliError::Decode(
+                "ciphertext too short to contain the nonce".into(),
+            ));
+        }
+        let nonce: Vec<u8> = ciphertext.drain(..opts.machine.rotors).collect();
+        mix_nonce(&mut state, &nonce, opts.machine.steps);
+    }
 
     let mut plaintext = Vec::with_capacity(ciphertext.len());
 
@@ -87,37 +148,138 @@ fn run_decrypt(opts: CommandOptions) {
         for (i, &b) in ciphertext.iter().enumerate() {
             println!("[{}] byte {}", i, b);
             println!(
-                "  state before: pos={:?}, step={}",
-                state.rotor_positions, state.step_counter
+                "  state before: {}",
+                format_state(&state, opts.position_format)
             );
 
-            let out = machine
-                .process_byte(b, &mut state)
-                .expect("decryption failed");
+            let out = machine.process_byte(b, &mut state)?;
 
             println!("  output char: '{}' ({})", out as char, out);
             println!(
-                "  state after:  pos={:?}, step={}",
-                state.rotor_positions, state.step_counter
+                "  state after:  {}",
+                format_state(&state, opts.position_format)
             );
             println!();
 
             plaintext.push(out);
         }
     } else {
-        plaintext = machine
-            .process_bytes(&ciphertext, &mut state)
-            .expect("decryption failed");
+        plaintext = machine.process_bytes(&ciphertext, &mut state)?;
+    }
+
+    if opts.pad.is_some() {
+        plaintext = unpad(&plaintext).ok_or_else(|| {
+            CliError::Decode("invalid padding: recorded length does not match".into())
+        })?;
     }
 
     println!("{}", String::from_utf8_lossy(&plaintext));
+
+    Ok(())
+}
+
+fn run_show_alphabet(opts: ShowAlphabetOptions) -> CliResult<()> {
+    let (machine, state) = setup(&opts.machine, false)?;
+    let (from, to) = parse_range(&opts.range)?;
+
+    let table = machine.compile_table(&state)?;
+
+    for b in from..=to {
+        let out = table[b as usize];
+        println!(
+            "{:>3} ({}) -> {:>3} ({})",
+            b,
+            printable(b),
+            out,
+            printable(out)
+        );
+    }
+
+    Ok(())
+}
+
+fn run_interactive(opts: ReplOptions) -> CliResult<()> {
+    let (machine, state) = setup(&opts.machine, false)?;
+    let formatter = build_formatter(opts.encoding, None, None);
+
+    let stdin = std::io::stdin();
+    run_repl(
+        machine,
+        state,
+        opts.machine.steps,
+        formatter.as_ref(),
+        opts.position_format,
+        stdin.lock(),
+        std::io::stdout(),
+    )?;
+
+    Ok(())
+}
+
+/// Print one key sheet line per day.
+///
+/// Each line is `date=YYYYMMDD rotors=N seed=S`; the seed drives
+/// seed-mode rotors and the initial positions of the daily machine.
+fn run_keygen(opts: KeygenOptions) -> CliResult<()> {
+    let master = resolve_seed(opts.seed, opts.seed_file.as_deref())?
+        .ok_or_else(|| CliError::Config("keygen requires --seed or --seed-file".into()))?;
+
+    let from = Date::parse(&opts.from).map_err(CliError::Config)?;
+    let to = Date::parse(&opts.to).map_err(CliError::Config)?;
+
+    for date in date_range(from, to).map_err(CliError::Config)? {
+        println!(
+            "date={} rotors={} seed={}",
+            date.as_number(),
+            opts.rotors,
+            day_seed(master, date)
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse a `FROM-TO` byte range.
+fn parse_range(s: &str) -> CliResult<(u8, u8)> {
+    let config = |msg: &str| CliError::Config(format!("{msg}: {s}"));
+
+    let (from, to) = s
+        .split_once('-')
+        .ok_or_else(|| config("range must be FROM-TO"))?;
+    let from: u8 = from.parse().map_err(|_| config("invalid range start"))?;
+    let to: u8 = to.parse().map_err(|_| config("invalid range end"))?;
+
+    if from > to {
+        return Err(config("range start must not exceed range end"));
+    }
+
+    Ok((from, to))
 }
 
-fn main() {
+fn printable(b: u8) -> char {
+    if b.is_ascii_graphic() || b == b' ' {
+        b as char
+    } else {
+        '.'
+    }
+}
+
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
-    match cli.command {
+    let result = match cli.command {
         Command::Encrypt(opts) => run_encrypt(opts),
         Command::Decrypt(opts) => run_decrypt(opts),
+        Command::ShowAlphabet(opts) => run_show_alphabet(opts),
+        Command::Keygen(opts) => run_keygen(opts),
+        Command::Repl(opts) => run_interactive(opts),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
     }
 }
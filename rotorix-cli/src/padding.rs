@@ -0,0 +1,60 @@
+use crate::error::{CliError, CliResult};
+use crate::random::random_bytes;
+
+/// Size of the length header prepended to padded plaintext.
+const HEADER_LEN: usize = 4;
+
+/// Pad plaintext to a multiple of `block` bytes.
+///
+/// The original length is stored as a big-endian `u32` header and
+/// the remainder is filled with random bytes, so the padding can be
+/// stripped exactly after decryption.
+pub fn pad(data: &[u8], block: usize) -> CliResult<Vec<u8>> {
+    let mut padded = Vec::with_capacity(HEADER_LEN + data.len() + block);
+    padded.extend_from_slice(&length_header(data.len())?);
+    padded.extend_from_slice(data);
+
+    let fill = (block - padded.len() % block) % block;
+    padded.extend(random_bytes(fill)?);
+
+    Ok(padded)
+}
+
+/// Encode `len` as the big-endian length header.
+fn length_header(len: usize) -> CliResult<[u8; HEADER_LEN]> {
+    u32::try_from(len)
+        .map(u32::to_be_bytes)
+        .map_err(|_| CliError::Config(format!("input of {len} bytes is too long to pad")))
+}
+
+/// Strip padding added by [`pad`], returning the original plaintext.
+///
+/// Returns `None` if the header is missing or the recorded length
+/// does not fit in the data.
+pub fn unpad(data: &[u8]) -> Option<Vec<u8>> {
+    let header: [u8; HEADER_LEN] = data.get(..HEADER_LEN)?.try_into().ok()?;
+    let len = u32::from_be_bytes(header) as usize;
+
+    data.get(HEADER_LEN..HEADER_LEN + len).map(<[u8]>::to_vec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn length_header_is_big_endian() {
+        assert_eq!(length_header(0x0102_0304).unwrap(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn oversized_input_is_a_config_error() {
+        let len = u32::MAX as usize + 1;
+
+        match length_header(len) {
+            Err(err @ CliError::Config(_)) => assert_eq!(err.exit_code(), 4),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
@@ -1,20 +1,65 @@
 use rotorix_core::Plugboard;
 
-pub fn build_plugboard(swap: Option<String>) -> Plugboard {
-    let mut mapping = [0u8; 256];
-    for (i, item) in mapping.iter_mut().enumerate() {
-        *item = i as u8;
+use crate::error::{CliError, CliResult};
+
+/// Build the plugboard from `--swap`, or the identity plugboard when
+/// `--no-plugboard` is given.
+pub fn build_plugboard(swap: Option<&str>, no_plugboard: bool) -> CliResult<Plugboard> {
+    if no_plugboard {
+        return Ok(Plugboard::identity());
     }
 
+    let mut pairs = Vec::new();
+
     if let Some(s) = swap {
-        let parts: Vec<_> = s.split(':').collect();
-        if parts.len() == 2 {
-            let a: u8 = parts[0].parse().expect("invalid swap value");
-            let b: u8 = parts[1].parse().expect("invalid swap value");
-            mapping[a as usize] = b;
-            mapping[b as usize] = a;
+        let Some((a, b)) = s.split_once(':').filter(|(_, b)| !b.contains(':')) else {
+            return Err(CliError::Config(format!(
+                "--swap '{s}' is not of the form A:B"
+            )));
+        };
+
+        let (a, b) = (parse_swap_value(a)?, parse_swap_value(b)?);
+        if a == b {
+            return Err(CliError::Config(format!(
+                "--swap '{s}' connects byte {a} to itself"
+            )));
         }
+
+        pairs.push((a, b));
+    }
+
+    Ok(Plugboard::from_pairs(&pairs)?)
+}
+
+/// Parse one side of `--swap`: a byte value (0–255) or a single
+/// ASCII character standing for its code.
+fn parse_swap_value(s: &str) -> CliResult<u8> {
+    if let Ok(value) = s.parse::<u64>() {
+        return u8::try_from(value).map_err(|_| {
+            CliError::Config(format!(
+                "--swap value {value} is out of range (expected 0–255)"
+            ))
+        });
+    }
+
+    match s.as_bytes() {
+        [c] if c.is_ascii() => Ok(*c),
+        _ => Err(CliError::Config(format!(
+            "--swap value '{s}' is neither a byte value (0–255) nor a single ASCII character"
+        ))),
+    }
+}
+
+/// Describe the effective plugboard for verbose output.
+pub fn describe_plugboard(plugboard: &Plugboard) -> String {
+    if plugboard.is_identity() {
+        return "identity".to_string();
     }
 
-    Plugboard::new(mapping).expect("invalid plugboard configuration")
+    plugboard
+        .pairs()
+        .iter()
+        .map(|(a, b)| format!("swap {a} <-> {b}"))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
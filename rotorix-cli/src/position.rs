@@ -0,0 +1,45 @@
+use clap::ValueEnum;
+use rotorix_core::EnigmaState;
+
+/// How rotor positions are displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PositionFormat {
+    /// Letters A–Z, falling back to numbers for wider moduli
+    Letters,
+    /// Decimal position values
+    Numbers,
+    /// Two-digit uppercase hex values
+    Hex,
+}
+
+/// Render the rotor positions of `state` for display.
+///
+/// `letters` falls back to `numbers` when a position has no letter,
+/// so wide stepping moduli still print something meaningful.
+fn format_positions(state: &EnigmaState, format: PositionFormat) -> String {
+    let numbers = || format!("{:?}", state.rotor_positions);
+
+    match format {
+        PositionFormat::Letters => state.rotor_letters().unwrap_or_else(numbers),
+        PositionFormat::Hex => state
+            .rotor_positions
+            .iter()
+            .map(|p| format!("{p:02X}"))
+            .collect::<Vec<_>>()
+            .join(" "),
+        PositionFormat::Numbers => numbers(),
+    }
+}
+
+/// Render `state` in the layout of its `Display` impl
+/// (`positions=[..] step=N`), with the positions in `format`.
+pub fn format_state(state: &EnigmaState, format: PositionFormat) -> String {
+    match format {
+        PositionFormat::Numbers => state.to_string(),
+        _ => format!(
+            "positions={} step={}",
+            format_positions(state, format),
+            state.step_counter
+        ),
+    }
+}
@@ -0,0 +1,12 @@
+use crate::error::{CliError, CliResult};
+
+/// Generate `len` random bytes from the operating system's RNG.
+///
+/// Used for nonces and padding, which must not be predictable.
+pub fn random_bytes(len: usize) -> CliResult<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    getrandom::fill(&mut bytes)
+        .map_err(|err| CliError::Io(format!("failed to read random bytes: {err}")))?;
+
+    Ok(bytes)
+}
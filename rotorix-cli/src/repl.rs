@@ -0,0 +1,93 @@
+use std::io::{self, BufRead, Write};
+
+use rotorix_core::{EnigmaMachine, EnigmaState};
+
+use crate::encoding::OutputFormatter;
+use crate::plugboard::{build_plugboard, describe_plugboard};
+use crate::position::{PositionFormat, format_state};
+
+const HELP: &str = "\
+commands:
+  <text>              encrypt a line and print the ciphertext
+  :decrypt <cipher>   decrypt a ciphertext and print the plaintext
+  :positions a,b,...  set the rotor positions
+  :swap A:B           rewire the plugboard (no argument clears it)
+  :state              show the current state
+  :reset              return to the initial state
+  :help               show this help
+  :quit               exit";
+
+/// Run an interactive session over `input`, writing results to `output`.
+///
+/// The state persists across lines, so consecutive lines behave like
+/// one continuous message until `:reset`. Positions set with
+/// `:positions` are checked against the stepping `modulus`, and
+/// `:swap` rewires the plugboard while keeping the state. Prompts go
+/// to stderr so the output stays scriptable.
+pub fn run_repl(
+    mut machine: EnigmaMachine,
+    initial: EnigmaState,
+    modulus: u32,
+    formatter: &dyn OutputFormatter,
+    position_format: PositionFormat,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    let mut state = initial.clone();
+
+    eprint!("rotorix> ");
+    for line in input.lines() {
+        let line = line?;
+
+        match line.split_once(' ').unwrap_or((line.as_str(), "")) {
+            (":quit", _) => break,
+            (":help", _) => writeln!(output, "{HELP}")?,
+            (":state", _) => {}
+            (":reset", _) => state = initial.clone(),
+            (":positions", args) => {
+                if let Err(e) = parse_positions(args)
+                    .and_then(|positions| state.set_positions(&positions, modulus))
+                {
+                    writeln!(output, "error: {e}")?;
+                }
+            }
+            (":swap", args) => {
+                let swap = Some(args.trim()).filter(|s| !s.is_empty());
+                match build_plugboard(swap, false) {
+                    Ok(plugboard) => {
+                        writeln!(output, "plugboard: {}", describe_plugboard(&plugboard))?;
+                        machine = machine.with_plugboard(Box::new(plugboard));
+                    }
+                    Err(e) => writeln!(output, "error: {e}")?,
+                }
+            }
+            (":decrypt", args) => match formatter.parse(args) {
+                Ok(ciphertext) => match machine.process_bytes(&ciphertext, &mut state) {
+                    Ok(plaintext) => writeln!(output, "{}", String::from_utf8_lossy(&plaintext))?,
+                    Err(e) => writeln!(output, "error: {e}")?,
+                },
+                Err(e) => writeln!(output, "error: {e}")?,
+            },
+            (cmd, _) if cmd.starts_with(':') => {
+                writeln!(output, "error: unknown command '{cmd}' (try :help)")?
+            }
+            _ => match machine.process_bytes(line.as_bytes(), &mut state) {
+                Ok(ciphertext) => writeln!(output, "{}", formatter.format(&ciphertext))?,
+                Err(e) => writeln!(output, "error: {e}")?,
+            },
+        }
+
+        writeln!(output, "  state: {}", format_state(&state, position_format))?;
+        eprint!("rotorix> ");
+    }
+
+    Ok(())
+}
+
+/// Parse a comma-separated list of rotor positions.
+fn parse_positions(s: &str) -> Result<Vec<u32>, String> {
+    s.split(',')
+        .map(|p| p.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("invalid position: {e}"))
+}
@@ -0,0 +1,29 @@
+use std::fs;
+use std::path::Path;
+
+use crate::error::{CliError, CliResult};
+
+/// Resolve the seed from `--seed` or `--seed-file`.
+pub fn resolve_seed(seed: Option<u64>, seed_file: Option<&Path>) -> CliResult<Option<u64>> {
+    match (seed, seed_file) {
+        (Some(seed), _) => Ok(Some(seed)),
+        (None, Some(path)) => read_seed_file(path).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Read a seed from a file, accepting decimal or `0x`-prefixed hex.
+pub fn read_seed_file(path: &Path) -> CliResult<u64> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| CliError::Io(format!("failed to read seed file {}: {e}", path.display())))?;
+
+    parse_seed(content.trim())
+        .ok_or_else(|| CliError::Config("seed file must contain a decimal or hex number".into()))
+}
+
+fn parse_seed(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
@@ -1,14 +1,40 @@
+use clap::ValueEnum;
 use rotorix_core::EnigmaState;
 
-pub fn build_state(rotors: usize, seed: Option<u64>) -> EnigmaState {
-    let mut state = EnigmaState::new(rotors);
+/// Byte order used to split the seed into rotor positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Endian {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
 
-    if let Some(seed) = seed {
-        for (i, pos) in state.rotor_positions.iter_mut().enumerate() {
-            // semplice derivazione deterministica
-            *pos = ((seed >> (i * 8)) & 0xFF) as u32;
-        }
-    }
+/// Build initial Enigma state, optionally seeded.
+///
+/// Each rotor position is taken from one byte of the seed, in the
+/// requested byte order, reduced modulo the stepping `modulus`.
+pub fn build_state(rotors: usize, seed: Option<u64>, endian: Endian, modulus: u32) -> EnigmaState {
+    let Some(seed) = seed else {
+        return EnigmaState::new(rotors);
+    };
 
-    state
+    let seed = match endian {
+        Endian::Little => seed,
+        Endian::Big => seed.swap_bytes(),
+    };
+
+    EnigmaState::from_seed(rotors, seed, modulus)
+}
+
+/// Mix a per-message nonce into the rotor positions.
+///
+/// Each nonce byte offsets the matching rotor position, wrapping at
+/// `modulus`. The nonce travels in clear ahead of the ciphertext.
+pub fn mix_nonce(state: &mut EnigmaState, nonce: &[u8], modulus: u32) {
+    assert!(modulus > 0, "stepping modulus must be greater than zero");
+
+    for (pos, &n) in state.rotor_positions.iter_mut().zip(nonce) {
+        *pos = ((u64::from(*pos) + u64::from(n)) % u64::from(modulus)) as u32;
+    }
 }
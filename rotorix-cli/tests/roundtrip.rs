@@ -70,3 +70,668 @@ fn roundtrip_base64() {
     let output = encrypt_then_decrypt(input, "base64");
     assert_eq!(output, input);
 }
+
+fn temp_seed_file(name: &str, content: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("rotorix-{}-{}", std::process::id(), name));
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+fn run(args: &[&str]) -> String {
+    let output = cargo_bin_cmd!("rotorix")
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    String::from_utf8_lossy(&output).trim().to_string()
+}
+
+#[test]
+fn roundtrip_seed_file() {
+    let path = temp_seed_file("seed-hex", "0x3039\n");
+    let path_str = path.to_str().unwrap();
+
+    let common = [
+        "--rotors",
+        "3",
+        "--rotor-mode",
+        "seed",
+        "--seed-file",
+        path_str,
+    ];
+
+    let ciphertext = run(&[&["encrypt", "HELLOENIGMA123"][..], &common].concat());
+
+    // The file holds 12345 in hex, so it must match `--seed 12345`
+    let expected = run(&[
+        "encrypt",
+        "HELLOENIGMA123",
+        "--rotors",
+        "3",
+        "--rotor-mode",
+        "seed",
+        "--seed",
+        "12345",
+    ]);
+    assert_eq!(ciphertext, expected);
+
+    let plaintext = run(&[&["decrypt", &ciphertext][..], &common].concat());
+    assert_eq!(plaintext, "HELLOENIGMA123");
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn seed_file_rejects_non_numeric_content() {
+    let path = temp_seed_file("seed-bad", "not a seed\n");
+
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "HELLO", "--seed-file", path.to_str().unwrap()])
+        .assert()
+        .failure();
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn seed_and_seed_file_are_mutually_exclusive() {
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "HELLO", "--seed", "1", "--seed-file", "seed.txt"])
+        .assert()
+        .failure();
+}
+
+fn initial_positions(endian: &str) -> String {
+    // 0x0102030405060708
+    let trace = run(&[
+        "encrypt",
+        "A",
+        "--rotors",
+        "3",
+        "--seed",
+        "72623859790382856",
+        "--endian",
+        endian,
+        "--trace",
+    ]);
+
+    trace
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("state before: "))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn seed_endianness_selects_position_bytes() {
+    let little = initial_positions("little");
+    let big = initial_positions("big");
+
+    assert_eq!(little, "positions=[8, 7, 6] step=0");
+    assert_eq!(big, "positions=[1, 2, 3] step=0");
+
+    // Reproducible across invocations
+    assert_eq!(initial_positions("big"), big);
+}
+
+fn initial_positions_as(format: &str) -> String {
+    let trace = run(&[
+        "encrypt",
+        "A",
+        "--rotors",
+        "3",
+        "--seed",
+        "72623859790382856",
+        "--endian",
+        "big",
+        "--trace",
+        "--position-format",
+        format,
+    ]);
+
+    trace
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("state before: "))
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn position_format_renders_known_state() {
+    assert_eq!(
+        initial_positions_as("numbers"),
+        "positions=[1, 2, 3] step=0"
+    );
+    assert_eq!(initial_positions_as("letters"), "positions=BCD step=0");
+    assert_eq!(initial_positions_as("hex"), "positions=01 02 03 step=0");
+
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--position-format", "roman"])
+        .assert()
+        .failure();
+}
+
+fn verbose_stderr(extra: &[&str]) -> String {
+    let output = cargo_bin_cmd!("rotorix")
+        .args([&["encrypt", "HELLO", "--verbose"][..], extra].concat())
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+
+    String::from_utf8_lossy(&output).to_string()
+}
+
+#[test]
+fn no_plugboard_and_omitted_swap_report_identity() {
+    assert!(verbose_stderr(&["--no-plugboard"]).contains("plugboard: identity"));
+    assert!(verbose_stderr(&[]).contains("plugboard: identity"));
+    assert!(verbose_stderr(&["--swap", "65:66"]).contains("plugboard: swap 65 <-> 66"));
+}
+
+#[test]
+fn verbose_output_describes_the_built_plugboard() {
+    assert!(verbose_stderr(&["--swap", "B:A"]).contains("plugboard: swap 65 <-> 66"));
+}
+
+#[test]
+fn no_plugboard_conflicts_with_swap() {
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "HELLO", "--no-plugboard", "--swap", "65:66"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn show_alphabet_matches_single_byte_encryption() {
+    let machine = [
+        "--rotors",
+        "3",
+        "--seed",
+        "12345",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+    ];
+
+    let table = run(&[&["show-alphabet", "--range", "65-90"][..], &machine].concat());
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 26);
+
+    for line in lines {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let input: u8 = fields[0].parse().unwrap();
+        let output: u8 = fields[3].parse().unwrap();
+
+        let single = (input as char).to_string();
+        let hex = run(&[&["encrypt", &single, "--encoding", "hex"][..], &machine].concat());
+
+        assert_eq!(hex, format!("{output:02X}"), "mismatch for {line}");
+    }
+}
+
+#[test]
+fn roundtrip_with_padding() {
+    let machine = ["--rotors", "2", "--seed", "777", "--rotor-mode", "seed"];
+
+    let ciphertext = run(&[
+        &["encrypt", "HELLO", "--pad", "16", "--encoding", "hex"][..],
+        &machine,
+    ]
+    .concat());
+
+    // 5 bytes of input plus the length header fit in one 16-byte block
+    assert_eq!(ciphertext.len(), 32);
+
+    let plaintext = run(&[
+        &["decrypt", &ciphertext, "--pad", "16", "--encoding", "hex"][..],
+        &machine,
+    ]
+    .concat());
+    assert_eq!(plaintext, "HELLO");
+}
+
+#[test]
+fn pad_rejects_zero_block_size() {
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "HELLO", "--pad", "0"])
+        .assert()
+        .failure();
+}
+
+fn decrypt_wrapped(encoding: &str) -> String {
+    let machine = ["--rotors", "2", "--seed", "4242", "--rotor-mode", "seed"];
+    let input = "THE QUICK BROWN FOX";
+
+    let ciphertext = run(&[&["encrypt", input, "--encoding", encoding][..], &machine].concat());
+
+    // Simulate a wrapped, indented paste
+    let (head, tail) = ciphertext.split_at(ciphertext.len() / 2);
+    let wrapped = format!(
+        "  {}\n{} \r\n",
+        head,
+        tail.replace(&tail[..2], &format!("{} ", &tail[..2]))
+    );
+
+    run(&[&["decrypt", &wrapped, "--encoding", encoding][..], &machine].concat())
+}
+
+#[test]
+fn decode_ignores_whitespace_hex() {
+    assert_eq!(decrypt_wrapped("hex"), "THE QUICK BROWN FOX");
+}
+
+#[test]
+fn decode_ignores_whitespace_base64() {
+    assert_eq!(decrypt_wrapped("base64"), "THE QUICK BROWN FOX");
+}
+
+#[test]
+fn roundtrip_with_skip() {
+    let machine = [
+        "--rotors",
+        "2",
+        "--seed",
+        "99",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+    ];
+
+    let skipped = run(&[&["encrypt", "HELLOENIGMA", "--skip", "5"][..], &machine].concat());
+    let unskipped = run(&[&["encrypt", "HELLOENIGMA", "--skip", "0"][..], &machine].concat());
+    assert_ne!(skipped, unskipped);
+
+    let plaintext = run(&[&["decrypt", &skipped, "--skip", "5"][..], &machine].concat());
+    assert_eq!(plaintext, "HELLOENIGMA");
+}
+
+#[test]
+fn roundtrip_with_nonce() {
+    let machine = [
+        "--rotors",
+        "3",
+        "--seed",
+        "12345",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+        "--encoding",
+        "hex",
+        "--nonce",
+    ];
+
+    let first = run(&[&["encrypt", "SAME PLAINTEXT"][..], &machine].concat());
+    let second = run(&[&["encrypt", "SAME PLAINTEXT"][..], &machine].concat());
+
+    // One clear nonce byte per rotor precedes the ciphertext
+    assert_eq!(first.len(), 2 * (3 + "SAME PLAINTEXT".len()));
+    assert_ne!(first, second);
+
+    for ciphertext in [&first, &second] {
+        let plaintext = run(&[&["decrypt", ciphertext][..], &machine].concat());
+        assert_eq!(plaintext, "SAME PLAINTEXT");
+    }
+}
+
+#[test]
+fn grouped_and_tagged_base32_roundtrip() {
+    let machine = [
+        "--rotors",
+        "2",
+        "--seed",
+        "31337",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+        "--encoding",
+        "base32",
+        "--group",
+        "4",
+        "--tag",
+        "RTX",
+    ];
+
+    let ciphertext = run(&[&["encrypt", "GROUPED OUTPUT"][..], &machine].concat());
+
+    let body = ciphertext.strip_prefix("RTX:").unwrap();
+    let groups: Vec<&str> = body.split(' ').collect();
+    assert!(groups.len() > 1);
+    assert!(groups[..groups.len() - 1].iter().all(|g| g.len() == 4));
+
+    let plaintext = run(&[&["decrypt", &ciphertext][..], &machine].concat());
+    assert_eq!(plaintext, "GROUPED OUTPUT");
+}
+
+#[test]
+fn decrypt_rejects_missing_tag() {
+    cargo_bin_cmd!("rotorix")
+        .args(["decrypt", "ABCD", "--encoding", "hex", "--tag", "RTX"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn keygen_emits_distinct_valid_daily_machines() {
+    let sheet = run(&[
+        "keygen", "--from", "20240228", "--to", "20240301", "--seed", "2024",
+    ]);
+
+    let lines: Vec<&str> = sheet.lines().collect();
+    // 2024 is a leap year: 28 Feb, 29 Feb, 1 Mar
+    assert_eq!(lines.len(), 3);
+
+    let mut seeds = Vec::new();
+    for (line, date) in lines.iter().zip(["20240228", "20240229", "20240301"]) {
+        let fields: std::collections::HashMap<&str, &str> = line
+            .split_whitespace()
+            .map(|kv| kv.split_once('=').unwrap())
+            .collect();
+
+        assert_eq!(fields["date"], date);
+        let rotors = fields["rotors"];
+        let seed = fields["seed"];
+        seeds.push(seed.to_string());
+
+        let machine = [
+            "--rotors",
+            rotors,
+            "--seed",
+            seed,
+            "--rotor-mode",
+            "seed",
+            "--reflector-mode",
+            "paired",
+        ];
+        let ciphertext = run(&[&["encrypt", "DAILY KEY"][..], &machine].concat());
+        let plaintext = run(&[&["decrypt", &ciphertext][..], &machine].concat());
+        assert_eq!(plaintext, "DAILY KEY");
+    }
+
+    seeds.sort();
+    seeds.dedup();
+    assert_eq!(seeds.len(), 3);
+}
+
+#[test]
+fn keygen_rejects_invalid_dates() {
+    for (from, to) in [
+        ("20230229", "20230301"),
+        ("20240301", "20240228"),
+        ("2024-3-1", "20240302"),
+        ("20240101", "20260101"),
+    ] {
+        cargo_bin_cmd!("rotorix")
+            .args(["keygen", "--from", from, "--to", to, "--seed", "1"])
+            .assert()
+            .failure();
+    }
+}
+
+#[test]
+fn repl_keeps_state_between_lines_until_reset() {
+    let machine = [
+        "--rotors",
+        "2",
+        "--seed",
+        "555",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+        "--encoding",
+        "hex",
+    ];
+
+    let output = cargo_bin_cmd!("rotorix")
+        .args([&["repl"][..], &machine].concat())
+        .write_stdin("HELLO\nHELLO\n:reset\nHELLO\n:quit\nIGNORED\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8_lossy(&output);
+
+    let results: Vec<&str> = output
+        .lines()
+        .filter(|l| !l.starts_with("  state:"))
+        .collect();
+    assert_eq!(results.len(), 3);
+
+    // State persists across lines...
+    assert_ne!(results[0], results[1]);
+    // ...until it is reset
+    assert_eq!(results[0], results[2]);
+
+    // A fresh session matches the one-shot command
+    let one_shot = run(&[&["encrypt", "HELLO"][..], &machine].concat());
+    assert_eq!(results[0], one_shot);
+
+    // :reset returns to the seeded start (555 = 0x022B)
+    assert!(output.contains("  state: positions=[43, 2] step=0"));
+}
+
+#[test]
+fn repl_decrypts_and_repositions() {
+    let machine = [
+        "--rotors",
+        "2",
+        "--rotor-mode",
+        "shifted",
+        "--reflector-mode",
+        "paired",
+        "--encoding",
+        "hex",
+    ];
+    let ciphertext = run(&[&["encrypt", "HI"][..], &machine].concat());
+
+    let output = cargo_bin_cmd!("rotorix")
+        .args([&["repl"][..], &machine].concat())
+        .write_stdin(format!(":positions 3,4\n:reset\n:decrypt {ciphertext}\n"))
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8_lossy(&output);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines[0], "  state: positions=[3, 4] step=0");
+    assert_eq!(lines[1], "  state: positions=[0, 0] step=0");
+    assert_eq!(lines[2], "HI");
+}
+
+#[test]
+fn repl_rejects_out_of_range_positions() {
+    let output = cargo_bin_cmd!("rotorix")
+        .args(["repl", "--rotors", "2", "--steps", "26"])
+        .write_stdin(":positions 3,26\n:positions 3\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8_lossy(&output);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines[0],
+        "error: position 26 of rotor 1 is out of range (modulus 26)"
+    );
+    assert_eq!(lines[1], "  state: positions=[0, 0] step=0");
+    assert_eq!(lines[2], "error: expected 2 position(s), got 1");
+}
+
+#[test]
+fn repl_swap_rewires_the_plugboard() {
+    let machine = ["--rotor-mode", "shifted", "--encoding", "hex"];
+    let swapped = run(&[&["encrypt", "HI", "--swap", "H:Q"][..], &machine].concat());
+    let plain = run(&[&["encrypt", "HI"][..], &machine].concat());
+
+    let output = cargo_bin_cmd!("rotorix")
+        .args([&["repl"][..], &machine].concat())
+        .write_stdin(":swap H:Q\nHI\n:reset\n:swap\nHI\n:swap H:H\n")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8_lossy(&output);
+
+    let results: Vec<&str> = output
+        .lines()
+        .filter(|l| !l.starts_with("  state:"))
+        .collect();
+    assert_eq!(results[0], "plugboard: swap 72 <-> 81");
+    assert_eq!(results[1], swapped);
+    assert_eq!(results[2], "plugboard: identity");
+    assert_eq!(results[3], plain);
+    assert!(results[4].starts_with("error: configuration error: --swap"));
+}
+
+#[test]
+fn error_classes_map_to_exit_codes() {
+    // Decode: not valid hex
+    cargo_bin_cmd!("rotorix")
+        .args(["decrypt", "ZZ", "--encoding", "hex"])
+        .assert()
+        .code(3);
+
+    // Usage: clap rejects an unknown encoding
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--encoding", "rot13"])
+        .assert()
+        .code(2);
+
+    // Config: --rotor-seeds without seed mode
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--rotor-seeds", "1"])
+        .assert()
+        .code(4);
+
+    // Machine: the core rejects a zero stepping modulus
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--steps", "0"])
+        .assert()
+        .code(5);
+
+    // I/O: unreadable seed file
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--seed-file", "/nonexistent/seed.txt"])
+        .assert()
+        .code(6);
+}
+
+fn with_rotor_seeds(command: &str, input: &str, seeds: &str) -> String {
+    run(&[
+        command,
+        input,
+        "--rotors",
+        "3",
+        "--rotor-mode",
+        "seed",
+        "--reflector-mode",
+        "paired",
+        "--rotor-seeds",
+        seeds,
+    ])
+}
+
+#[test]
+fn roundtrip_with_per_rotor_seeds() {
+    let plaintext = "PER ROTOR SEEDS";
+
+    let ciphertext = with_rotor_seeds("encrypt", plaintext, "11,22,33");
+    assert_eq!(
+        with_rotor_seeds("decrypt", &ciphertext, "11,22,33"),
+        plaintext
+    );
+
+    // Same seeds on different rotors give a different machine
+    assert_ne!(
+        with_rotor_seeds("encrypt", plaintext, "33,22,11"),
+        ciphertext
+    );
+
+    // Seed count must match the rotor count
+    cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A", "--rotors", "3", "--rotor-mode", "seed"])
+        .args(["--rotor-seeds", "1,2"])
+        .assert()
+        .code(4);
+}
+
+#[test]
+fn short_period_machine_warns() {
+    let stderr = |steps: &str| {
+        let output = cargo_bin_cmd!("rotorix")
+            .args(["encrypt", "A", "--rotors", "2", "--steps", steps])
+            .assert()
+            .success()
+            .get_output()
+            .stderr
+            .clone();
+        String::from_utf8_lossy(&output).to_string()
+    };
+
+    assert!(stderr("4").contains("warning: rotor positions repeat"));
+    assert!(!stderr("256").contains("warning"));
+}
+
+#[test]
+fn default_machine_does_not_warn() {
+    let output = cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "A"])
+        .assert()
+        .success()
+        .get_output()
+        .stderr
+        .clone();
+
+    assert!(!String::from_utf8_lossy(&output).contains("warning"));
+}
+
+#[test]
+fn malformed_swap_is_a_config_error() {
+    for swap in ["1:2:3", "1-2", "65:A", "A:A", ":B"] {
+        let output = cargo_bin_cmd!("rotorix")
+            .args(["encrypt", "HELLO", "--swap", swap])
+            .assert()
+            .code(4)
+            .get_output()
+            .stderr
+            .clone();
+
+        assert!(String::from_utf8_lossy(&output).contains("--swap"));
+    }
+}
+
+#[test]
+fn out_of_range_swap_value_is_a_clean_error() {
+    let output = cargo_bin_cmd!("rotorix")
+        .args(["encrypt", "HELLO", "--swap", "A:300"])
+        .assert()
+        .code(4)
+        .get_output()
+        .stderr
+        .clone();
+    let stderr = String::from_utf8_lossy(&output);
+
+    assert!(stderr.contains("--swap value 300 is out of range (expected 0–255)"));
+    assert!(!stderr.contains("panicked"));
+
+    // Characters stand for their byte value
+    assert_eq!(
+        run(&["encrypt", "A", "--swap", "A:B", "--encoding", "hex"]),
+        run(&["encrypt", "A", "--swap", "65:66", "--encoding", "hex"])
+    );
+}
@@ -0,0 +1,49 @@
+//! Statistical analysis helpers.
+//!
+//! These helpers operate on output bytes only and are intended for
+//! experimentation and teaching, not for security assessment.
+
+/// Byte distribution statistics of a ciphertext.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionStats {
+    /// Number of occurrences of each byte value.
+    pub frequencies: [u64; 256],
+
+    /// Total number of bytes analyzed.
+    pub total: usize,
+
+    /// Pearson's chi-square statistic against the uniform distribution.
+    ///
+    /// Values close to 255 (the degrees of freedom) indicate a
+    /// near-uniform output; much larger values indicate bias.
+    pub chi_square: f64,
+}
+
+/// Computes byte frequencies and the chi-square statistic of `ciphertext`.
+///
+/// An empty input yields all-zero frequencies and a chi-square of 0.
+pub fn analyze_distribution(ciphertext: &[u8]) -> DistributionStats {
+    let mut frequencies = [0u64; 256];
+    for &b in ciphertext {
+        frequencies[b as usize] += 1;
+    }
+
+    let chi_square = if ciphertext.is_empty() {
+        0.0
+    } else {
+        let expected = ciphertext.len() as f64 / 256.0;
+        frequencies
+            .iter()
+            .map(|&observed| {
+                let d = observed as f64 - expected;
+                d * d / expected
+            })
+            .sum()
+    };
+
+    DistributionStats {
+        frequencies,
+        total: ciphertext.len(),
+        chi_square,
+    }
+}
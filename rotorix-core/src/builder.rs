@@ -0,0 +1,190 @@
+//! Builder for `EnigmaMachine`.
+//!
+//! The builder collects components step by step and can optionally
+//! capture the initial `EnigmaState`, guaranteeing that the state
+//! matches the machine it is built with.
+
+use crate::{
+    component::EnigmaComponent,
+    error::{EnigmaError, EnigmaResult},
+    machine::EnigmaMachine,
+    plugboard::Plugboard,
+    state::EnigmaState,
+    stepping::{LinearStepping, SteppingStrategy},
+};
+
+/// Step-by-step constructor for `EnigmaMachine`.
+///
+/// Defaults:
+/// - plugboard: identity
+/// - stepping: `LinearStepping` with modulus 256
+///
+/// At least one rotor and a reflector are required.
+#[derive(Default)]
+pub struct EnigmaMachineBuilder {
+    plugboard: Option<Box<dyn EnigmaComponent>>,
+    rotors: Vec<Box<dyn EnigmaComponent>>,
+    reflector: Option<Box<dyn EnigmaComponent>>,
+    stepping: Option<Box<dyn SteppingStrategy>>,
+    state: Option<EnigmaState>,
+}
+
+impl EnigmaMachineBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the plugboard.
+    pub fn plugboard(mut self, plugboard: Box<dyn EnigmaComponent>) -> Self {
+        self.plugboard = Some(plugboard);
+        self
+    }
+
+    /// Appends a rotor (rotors are ordered left to right).
+    pub fn rotor(mut self, rotor: Box<dyn EnigmaComponent>) -> Self {
+        self.rotors.push(rotor);
+        self
+    }
+
+    /// Sets the reflector.
+    pub fn reflector(mut self, reflector: Box<dyn EnigmaComponent>) -> Self {
+        self.reflector = Some(reflector);
+        self
+    }
+
+    /// Sets the stepping strategy.
+    pub fn stepping(mut self, stepping: Box<dyn SteppingStrategy>) -> Self {
+        self.stepping = Some(stepping);
+        self
+    }
+
+    /// Sets the initial rotor positions.
+    ///
+    /// The step counter starts at zero.
+    pub fn positions(mut self, positions: Vec<u32>) -> Self {
+        self.state = Some(EnigmaState {
+            rotor_positions: positions,
+            step_counter: 0,
+        });
+        self
+    }
+
+    /// Sets the full initial state.
+    pub fn initial_state(mut self, state: EnigmaState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Runs structural checks on the collected components and reports
+    /// every problem at once.
+    ///
+    /// Checks that there is at least one rotor, that the reflector is
+    /// present and pairs bytes symmetrically, that the plugboard is
+    /// symmetric, and that any captured state has one position per
+    /// rotor. Component checks are made at the zero state.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidConfiguration` listing all the
+    /// problems found, separated by `; `.
+    pub fn validate(&self) -> EnigmaResult<()> {
+        let rotor_count = self.rotors.len();
+        let zero = EnigmaState::new(rotor_count);
+        let mut problems = Vec::new();
+
+        if rotor_count == 0 {
+            problems.push("at least one rotor is required".to_string());
+        }
+
+        match &self.reflector {
+            None => problems.push("a reflector is required".to_string()),
+            Some(reflector) => {
+                if let Some(b) = first_non_involutive(reflector.as_ref(), &zero) {
+                    problems.push(format!("reflector is not an involution (byte {b})"));
+                }
+            }
+        }
+
+        if let Some(plugboard) = &self.plugboard
+            && let Some(b) = first_non_involutive(plugboard.as_ref(), &zero)
+        {
+            problems.push(format!("plugboard is not an involution (byte {b})"));
+        }
+
+        if let Some(state) = &self.state
+            && state.rotor_positions.len() != rotor_count
+        {
+            problems.push(format!(
+                "initial state has {} rotor position(s) but the machine has {} rotor(s)",
+                state.rotor_positions.len(),
+                rotor_count
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(EnigmaError::InvalidConfiguration(format!(
+                "{} problem(s): {}",
+                problems.len(),
+                problems.join("; ")
+            )))
+        }
+    }
+
+    /// Builds the machine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required component is missing or the
+    /// configuration is invalid.
+    pub fn build(self) -> EnigmaResult<EnigmaMachine> {
+        self.build_with_state().map(|(machine, _)| machine)
+    }
+
+    /// Builds the machine together with its initial state.
+    ///
+    /// Without explicit positions, the state starts with all rotors
+    /// at position zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a required component is missing, the
+    /// configuration is invalid, or the captured state does not have
+    /// one position per rotor.
+    pub fn build_with_state(self) -> EnigmaResult<(EnigmaMachine, EnigmaState)> {
+        let rotor_count = self.rotors.len();
+
+        let state = self.state.unwrap_or_else(|| EnigmaState::new(rotor_count));
+
+        if state.rotor_positions.len() != rotor_count {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "initial state has {} rotor position(s) but the machine has {} rotor(s)",
+                state.rotor_positions.len(),
+                rotor_count
+            )));
+        }
+
+        let reflector = self
+            .reflector
+            .ok_or_else(|| EnigmaError::InvalidConfiguration("a reflector is required".into()))?;
+
+        let machine = EnigmaMachine::new(
+            self.plugboard
+                .unwrap_or_else(|| Box::new(Plugboard::identity())),
+            self.rotors,
+            reflector,
+            self.stepping
+                .unwrap_or_else(|| Box::new(LinearStepping::unchecked(256))),
+        )?;
+
+        Ok((machine, state))
+    }
+}
+
+/// Returns the first byte that the component's forward mapping does
+/// not send back to itself when applied twice.
+fn first_non_involutive(component: &dyn EnigmaComponent, state: &EnigmaState) -> Option<u8> {
+    (0..=255u8).find(|&b| component.forward(component.forward(b, state), state) != b)
+}
@@ -0,0 +1,91 @@
+//! Cascades of Enigma machines.
+//!
+//! A cascade layers several machines: the output of one stage is the
+//! input of the next. Each stage keeps its own independent state.
+
+use crate::{
+    error::{EnigmaError, EnigmaResult},
+    machine::EnigmaMachine,
+    state::EnigmaState,
+};
+
+/// Independent per-stage states of a cascade.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct CascadeState {
+    /// One state per stage, in stage order.
+    pub states: Vec<EnigmaState>,
+}
+
+impl CascadeState {
+    /// Creates a cascade state from per-stage states.
+    pub fn new(states: Vec<EnigmaState>) -> Self {
+        Self { states }
+    }
+}
+
+/// A sequence of machines applied one after another.
+pub struct Cascade {
+    stages: Vec<EnigmaMachine>,
+}
+
+impl Cascade {
+    /// Creates a new cascade from its stages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no stage is provided.
+    pub fn new(stages: Vec<EnigmaMachine>) -> EnigmaResult<Self> {
+        if stages.is_empty() {
+            return Err(EnigmaError::InvalidConfiguration(
+                "a cascade requires at least one stage".into(),
+            ));
+        }
+
+        Ok(Self { stages })
+    }
+
+    /// Processes bytes through every stage in order.
+    ///
+    /// Each stage advances only its own state.
+    pub fn process_bytes(&self, input: &[u8], state: &mut CascadeState) -> EnigmaResult<Vec<u8>> {
+        self.check_state(state)?;
+
+        let mut data = input.to_vec();
+        for (stage, stage_state) in self.stages.iter().zip(&mut state.states) {
+            data = stage.process_bytes(&data, stage_state)?;
+        }
+
+        Ok(data)
+    }
+
+    /// Processes bytes through every stage in reverse order.
+    ///
+    /// With reflector-based stages and states matching those used for
+    /// encryption, this undoes [`Cascade::process_bytes`].
+    pub fn process_bytes_reverse(
+        &self,
+        input: &[u8],
+        state: &mut CascadeState,
+    ) -> EnigmaResult<Vec<u8>> {
+        self.check_state(state)?;
+
+        let mut data = input.to_vec();
+        for (stage, stage_state) in self.stages.iter().zip(&mut state.states).rev() {
+            data = stage.process_bytes(&data, stage_state)?;
+        }
+
+        Ok(data)
+    }
+
+    fn check_state(&self, state: &CascadeState) -> EnigmaResult<()> {
+        if state.states.len() != self.stages.len() {
+            return Err(EnigmaError::InvalidState(format!(
+                "cascade has {} stage(s) but {} state(s) were provided",
+                self.stages.len(),
+                state.states.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! Hex text form of 256-entry tables.
+//!
+//! Used to import and export wirings and as the compact serde
+//! representation of component tables.
+
+use crate::error::{EnigmaError, EnigmaResult};
+
+/// Returns `table` as 512 uppercase hex digits.
+pub(crate) fn encode_table(table: &[u8; 256]) -> String {
+    table.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+/// Parses 512 hex digits (surrounding whitespace ignored) into a table.
+///
+/// `name` describes the table in error messages.
+pub(crate) fn decode_table(s: &str, name: &str) -> EnigmaResult<[u8; 256]> {
+    let s = s.trim();
+
+    let count = s.chars().count();
+    if count != 512 {
+        return Err(EnigmaError::InvalidConfiguration(format!(
+            "{name} must be 512 hex digits (got {count})"
+        )));
+    }
+
+    let mut digits = [0u8; 512];
+    for (i, (d, c)) in digits.iter_mut().zip(s.chars()).enumerate() {
+        *d = c.to_digit(16).ok_or_else(|| {
+            EnigmaError::InvalidConfiguration(format!(
+                "{name} has non-hex character {c:?} at position {i}"
+            ))
+        })? as u8;
+    }
+
+    let mut table = [0u8; 256];
+    for (v, pair) in table.iter_mut().zip(digits.chunks_exact(2)) {
+        *v = pair[0] << 4 | pair[1];
+    }
+
+    Ok(table)
+}
+
+/// Serialized form of components whose state is one involutive
+/// mapping: `{ "mapping": "<512 hex digits>" }`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct MappingRepr {
+    mapping: String,
+}
+
+#[cfg(feature = "serde")]
+impl MappingRepr {
+    /// Encodes `mapping` as hex.
+    pub(crate) fn new(mapping: &[u8; 256]) -> Self {
+        Self {
+            mapping: encode_table(mapping),
+        }
+    }
+
+    /// Decodes the hex table; `name` describes it in error messages.
+    ///
+    /// Only the hex form is checked here; callers validate the table.
+    pub(crate) fn decode(&self, name: &str) -> EnigmaResult<[u8; 256]> {
+        decode_table(&self.mapping, name)
+    }
+}
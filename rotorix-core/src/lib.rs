@@ -10,10 +10,21 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+pub mod analysis;
+pub mod builder;
+pub mod cascade;
 pub mod component;
 pub mod error;
 pub mod machine;
+pub mod prng;
+pub mod recording;
 pub mod state;
+pub mod stream;
+
+#[cfg(feature = "test-support")]
+pub mod test_support;
+
+mod hex;
 
 // Core building blocks
 pub mod plugboard;
@@ -22,16 +33,24 @@ pub mod rotor;
 pub mod stepping;
 
 // Public re-exports (stable surface)
+pub use analysis::{DistributionStats, analyze_distribution};
+pub use builder::EnigmaMachineBuilder;
+pub use cascade::{Cascade, CascadeState};
 pub use component::EnigmaComponent;
 pub use error::{EnigmaError, EnigmaResult};
-pub use machine::EnigmaMachine;
+pub use machine::{EnigmaMachine, StateLengthPolicy};
+pub use recording::RecordingComponent;
 pub use state::EnigmaState;
-pub use stepping::SteppingStrategy;
+pub use stepping::{StepReport, SteppingStrategy};
+pub use stream::StreamCipher;
 
 // Concrete components
 pub use plugboard::Plugboard;
-pub use reflector::Reflector;
+pub use reflector::{MovingReflector, Reflector, SwitchingReflector};
 pub use rotor::Rotor;
 
 // Stepping strategies
-pub use stepping::LinearStepping;
+pub use stepping::{
+    AlphabetStepping, ChainedStepping, DoubleStepping, GappedStepping, LinearStepping,
+    NotchStepping, RandomStepping,
+};
@@ -3,30 +3,72 @@
 //! This module defines the `EnigmaMachine`, which wires together
 //! components, state, and stepping strategy into a transformation pipeline.
 
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::rc::Rc;
+
 use crate::{
     component::EnigmaComponent,
     error::{EnigmaError, EnigmaResult},
+    plugboard::Plugboard,
+    prng::{SplitMix64, derive_seed},
+    reflector::Reflector,
+    rotor::Rotor,
     state::EnigmaState,
-    stepping::SteppingStrategy,
+    stepping::{LinearStepping, SteppingStrategy},
 };
 
+/// Symbols traced by [`EnigmaMachine::to_dot`], kept small so the
+/// rendered graph stays readable.
+const DOT_SYMBOLS: std::ops::RangeInclusive<u8> = b'A'..=b'H';
+
+/// Number of swapped pairs on a passphrase-derived plugboard,
+/// matching the ten cables of the historical machine.
+const PASSPHRASE_PLUG_PAIRS: usize = 10;
+
+/// Policy applied when a state has more rotor positions than the
+/// machine has rotors.
+///
+/// A state with too few positions is always rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StateLengthPolicy {
+    /// Reject the state with `EnigmaError::InvalidState`.
+    #[default]
+    Strict,
+
+    /// Drop the surplus positions before processing.
+    Truncate,
+}
+
 /// Core Enigma transformation machine.
 ///
 /// The machine itself is stateless. All mutable data is contained
 /// in the external `EnigmaState`.
 pub struct EnigmaMachine {
-    plugboard: Box<dyn EnigmaComponent>,
-    rotors: Vec<Box<dyn EnigmaComponent>>,
-    reflector: Box<dyn EnigmaComponent>,
-    stepping: Box<dyn SteppingStrategy>,
+    plugboard: Rc<dyn EnigmaComponent>,
+    rotors: Vec<Rc<dyn EnigmaComponent>>,
+    reflector: Rc<dyn EnigmaComponent>,
+    stepping: Rc<dyn SteppingStrategy>,
+    state_policy: StateLengthPolicy,
+    otp: Option<Rc<[u8]>>,
+    step_before: bool,
 }
 
 impl EnigmaMachine {
+    /// Largest number of start positions [`EnigmaMachine::output_spread`]
+    /// will enumerate.
+    pub const MAX_SPREAD_KEYSPACE: u64 = 1 << 24;
+
+    /// Largest number of steps [`EnigmaMachine::is_weak_period`] will
+    /// take while looking for the state period.
+    pub const MAX_PERIOD_SEARCH: u64 = 1 << 24;
+
     /// Creates a new `EnigmaMachine` from its components.
     ///
     /// # Errors
     ///
-    /// Returns an error if the configuration is invalid.
+    /// Returns an error if the configuration is invalid, including a
+    /// stepping strategy rejected by [`SteppingStrategy::validate`].
     pub fn new(
         plugboard: Box<dyn EnigmaComponent>,
         rotors: Vec<Box<dyn EnigmaComponent>>,
@@ -39,27 +81,280 @@ impl EnigmaMachine {
             ));
         }
 
+        stepping
+            .validate(rotors.len())
+            .map_err(EnigmaError::InvalidConfiguration)?;
+
         Ok(Self {
-            plugboard,
-            rotors,
-            reflector,
-            stepping,
+            plugboard: plugboard.into(),
+            rotors: rotors.into_iter().map(Rc::from).collect(),
+            reflector: reflector.into(),
+            stepping: stepping.into(),
+            state_policy: StateLengthPolicy::default(),
+            otp: None,
+            step_before: false,
         })
     }
 
+    /// Sets the policy for states with surplus rotor positions.
+    pub fn with_state_policy(mut self, policy: StateLengthPolicy) -> Self {
+        self.state_policy = policy;
+        self
+    }
+
+    /// Chooses whether the state is stepped before or after each byte
+    /// is transformed.
+    ///
+    /// The historical Enigma moves its rotors before the key closes the
+    /// circuit, so `true` gives that behavior. The default is `false`:
+    /// the byte is transformed at the current state, which is stepped
+    /// afterwards. Encryption and decryption must use the same mode.
+    pub fn with_step_before(mut self, step_before: bool) -> Self {
+        self.step_before = step_before;
+        self
+    }
+
+    /// Replaces the plugboard, keeping every other component.
+    ///
+    /// Together with [`Plugboard::rewire`] this changes the wiring
+    /// mid-session without rebuilding the rotors and reflector.
+    pub fn with_plugboard(mut self, plugboard: Box<dyn EnigmaComponent>) -> Self {
+        self.plugboard = plugboard.into();
+        self
+    }
+
+    /// Adds a one-time pad XOR layer keyed by `key`.
+    ///
+    /// The key byte for each symbol is `key[step_counter % key.len()]`;
+    /// it is XORed into the input before the pipeline and into the
+    /// output after it, which keeps the machine self-inverse. The key
+    /// must be kept secret, and only a key at least as long as the
+    /// message, used once, gives one-time pad guarantees.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is empty.
+    pub fn with_otp(mut self, key: Vec<u8>) -> EnigmaResult<Self> {
+        if key.is_empty() {
+            return Err(EnigmaError::InvalidConfiguration(
+                "one-time pad key must not be empty".into(),
+            ));
+        }
+
+        self.otp = Some(key.into());
+        Ok(self)
+    }
+
+    /// Creates a new `EnigmaMachine`, rejecting self-enciphering configurations.
+    ///
+    /// In addition to the checks performed by [`EnigmaMachine::new`],
+    /// this verifies that at the zero state no byte maps to itself
+    /// (the classic reflector property).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error listing the offending bytes if any byte
+    /// enciphers to itself.
+    pub fn new_strict(
+        plugboard: Box<dyn EnigmaComponent>,
+        rotors: Vec<Box<dyn EnigmaComponent>>,
+        reflector: Box<dyn EnigmaComponent>,
+        stepping: Box<dyn SteppingStrategy>,
+    ) -> EnigmaResult<Self> {
+        let machine = Self::new(plugboard, rotors, reflector, stepping)?;
+
+        let fixed = machine.fixed_points(&EnigmaState::new(machine.rotors.len()))?;
+        if !fixed.is_empty() {
+            let bytes: Vec<String> = fixed.iter().map(u8::to_string).collect();
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "machine enciphers {} byte(s) to themselves: {}",
+                fixed.len(),
+                bytes.join(", ")
+            )));
+        }
+
+        Ok(machine)
+    }
+
+    /// Derives a machine and its initial state from a passphrase.
+    ///
+    /// A seed is derived from the passphrase by key stretching and
+    /// drives seed-based rotors, a seeded reflector and plugboard, and
+    /// the start positions. The same passphrase always yields the same
+    /// machine and state.
+    ///
+    /// The derivation is deterministic, not a password hash: it does
+    /// not make weak passphrases safe.
+    pub fn from_passphrase(passphrase: &str, rotor_count: usize) -> (EnigmaMachine, EnigmaState) {
+        let mut rng = SplitMix64::new(derive_seed(passphrase));
+
+        let rotors: Vec<Box<dyn EnigmaComponent>> = (0..rotor_count)
+            .map(|i| Box::new(Rotor::from_seed(i, rng.next_u64())) as Box<dyn EnigmaComponent>)
+            .collect();
+
+        let reflector = Reflector::random(rng.next_u64());
+        let plugboard = Plugboard::random(rng.next_u64(), PASSPHRASE_PLUG_PAIRS)
+            .expect("passphrase plugboard pair count is within bounds");
+
+        let mut state = EnigmaState::new(rotor_count);
+        for pos in &mut state.rotor_positions {
+            *pos = rng.below(256) as u32;
+        }
+
+        let machine = EnigmaMachine::new(
+            Box::new(plugboard),
+            rotors,
+            Box::new(reflector),
+            Box::new(LinearStepping::unchecked(256)),
+        )
+        .expect("passphrase-derived components are valid by construction");
+
+        (machine, state)
+    }
+
+    /// Compiles the substitution applied to every byte at the given state.
+    ///
+    /// Entry `i` is the output for input byte `i`. The state is not
+    /// stepped, so this is the machine's substitution alphabet for
+    /// one position.
+    pub fn compile_table(&self, state: &EnigmaState) -> EnigmaResult<[u8; 256]> {
+        self.check_state(state)?;
+
+        let mut table = [0u8; 256];
+        for (i, v) in table.iter_mut().enumerate() {
+            *v = self.transform(i as u8, state);
+        }
+
+        Ok(table)
+    }
+
+    /// Returns the bytes that encipher to themselves at the given state.
+    ///
+    /// The state is not stepped.
+    pub fn fixed_points(&self, state: &EnigmaState) -> EnigmaResult<Vec<u8>> {
+        let table = self.compile_table(state)?;
+
+        Ok((0..=255u8).filter(|&b| table[b as usize] == b).collect())
+    }
+
     /// Processes a single byte through the Enigma pipeline.
     ///
     /// The state is updated via the configured stepping strategy
-    /// after the transformation.
+    /// after the transformation, or before it if enabled with
+    /// [`EnigmaMachine::with_step_before`].
+    ///
+    /// The input byte is passed to
+    /// [`SteppingStrategy::step_with_input`].
+    ///
+    /// Under [`StateLengthPolicy::Truncate`], surplus rotor positions
+    /// are removed from `state` before processing.
     pub fn process_byte(&self, input: u8, state: &mut EnigmaState) -> EnigmaResult<u8> {
-        if state.rotor_positions.len() != self.rotors.len() {
+        self.prepare_state(state)?;
+
+        if self.step_before {
+            self.stepping
+                .step_with_input(state, input)
+                .map_err(EnigmaError::SteppingError)?;
+
+            return Ok(self.transform(input, state));
+        }
+
+        let value = self.transform(input, state);
+
+        // Step state AFTER processing
+        self.stepping
+            .step_with_input(state, input)
+            .map_err(EnigmaError::SteppingError)?;
+
+        Ok(value)
+    }
+
+    /// Steps the state `n` times without producing output.
+    ///
+    /// Used to discard the first outputs before processing real input
+    /// (a warm-up). Decryption must skip the same number of steps.
+    pub fn skip(&self, state: &mut EnigmaState, n: u64) -> EnigmaResult<()> {
+        self.prepare_state(state)?;
+
+        for _ in 0..n {
+            self.stepping
+                .step(state)
+                .map_err(EnigmaError::SteppingError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of steps after which the rotor positions
+    /// first return to those of `start`.
+    ///
+    /// The step counter is ignored. Returns `None` if the positions do
+    /// not recur within `limit` steps.
+    pub fn period(&self, start: &EnigmaState, limit: u64) -> EnigmaResult<Option<u64>> {
+        self.check_state(start)?;
+
+        let mut state = start.clone();
+        state.rotor_positions.truncate(self.rotors.len());
+
+        for n in 1..=limit {
+            self.stepping
+                .step(&mut state)
+                .map_err(EnigmaError::SteppingError)?;
+
+            if state.rotor_positions[..] == start.rotor_positions[..self.rotors.len()] {
+                return Ok(Some(n));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns `true` if the rotor positions repeat in fewer than
+    /// `threshold` steps from `start`.
+    ///
+    /// A short period means the keystream repeats quickly. The search
+    /// is capped at [`MAX_PERIOD_SEARCH`](Self::MAX_PERIOD_SEARCH)
+    /// steps, so larger thresholds behave like the cap.
+    pub fn is_weak_period(&self, start: &EnigmaState, threshold: u64) -> EnigmaResult<bool> {
+        let limit = threshold.saturating_sub(1).min(Self::MAX_PERIOD_SEARCH);
+
+        Ok(self.period(start, limit)?.is_some())
+    }
+
+    /// Checks `state` against the rotor count and, under a truncating
+    /// policy, drops the extra positions.
+    pub(crate) fn prepare_state(&self, state: &mut EnigmaState) -> EnigmaResult<()> {
+        self.check_state(state)?;
+        state.rotor_positions.truncate(self.rotors.len());
+
+        Ok(())
+    }
+
+    fn check_state(&self, state: &EnigmaState) -> EnigmaResult<()> {
+        let (positions, rotors) = (state.rotor_positions.len(), self.rotors.len());
+
+        let valid = match self.state_policy {
+            StateLengthPolicy::Strict => positions == rotors,
+            StateLengthPolicy::Truncate => positions >= rotors,
+        };
+
+        if !valid {
             return Err(EnigmaError::InvalidState(
                 "rotor position count does not match rotor count".into(),
             ));
         }
 
+        Ok(())
+    }
+
+    /// Runs a byte through the full pipeline without stepping.
+    fn transform(&self, input: u8, state: &EnigmaState) -> u8 {
+        let pad = self.otp.as_ref().map_or(0, |key| {
+            key[(state.step_counter % key.len() as u64) as usize]
+        });
+
         // Forward pass
-        let mut value = self.plugboard.forward(input, state);
+        let mut value = self.plugboard.forward(input ^ pad, state);
 
         for rotor in &self.rotors {
             value = rotor.forward(value, state);
@@ -73,14 +368,337 @@ impl EnigmaMachine {
             value = rotor.backward(value, state);
         }
 
-        value = self.plugboard.backward(value, state);
+        self.plugboard.backward(value, state) ^ pad
+    }
+
+    /// Returns a machine whose pipeline undoes this machine's pipeline.
+    ///
+    /// The plugboard and rotor passes are already mirrored around the
+    /// reflector, so only the reflector stage needs to be inverted.
+    /// For an involutive reflector the result behaves exactly like
+    /// this machine; for one-way machines (a non-involutive reflector
+    /// stage, e.g. a rotor) it is the decryption machine.
+    ///
+    /// The inverse only undoes a byte processed at the *same* state:
+    /// both machines must start from the same initial state and step
+    /// in lockstep. Components are shared, not copied.
+    pub fn inverse(&self) -> EnigmaResult<EnigmaMachine> {
+        Ok(Self {
+            plugboard: Rc::clone(&self.plugboard),
+            rotors: self.rotors.clone(),
+            reflector: Rc::new(Inverse(Rc::clone(&self.reflector))),
+            stepping: Rc::clone(&self.stepping),
+            state_policy: self.state_policy,
+            otp: self.otp.clone(),
+            step_before: self.step_before,
+        })
+    }
+
+    /// Measures the avalanche effect of flipping a single input bit.
+    ///
+    /// Encrypts `input` and a copy with bit `flip_bit` flipped (bit 0
+    /// is the least significant bit of the first byte), both from
+    /// `start`, and returns the fraction of output bits that differ.
+    ///
+    /// Since each byte is substituted independently, a flipped bit
+    /// only affects the byte that contains it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `flip_bit` is outside the input or if
+    /// processing fails.
+    pub fn avalanche(
+        &self,
+        input: &[u8],
+        start: &EnigmaState,
+        flip_bit: usize,
+    ) -> EnigmaResult<f64> {
+        if flip_bit >= input.len() * 8 {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "bit index {flip_bit} out of range for {} input byte(s)",
+                input.len()
+            )));
+        }
+
+        let mut flipped = input.to_vec();
+        flipped[flip_bit / 8] ^= 1 << (flip_bit % 8);
+
+        let a = self.process_bytes(input, &mut start.clone())?;
+        let b = self.process_bytes(&flipped, &mut start.clone())?;
+
+        let differing: u32 = a.iter().zip(&b).map(|(x, y)| (x ^ y).count_ones()).sum();
+
+        Ok(f64::from(differing) / (input.len() * 8) as f64)
+    }
+
+    /// Counts the distinct outputs `input` produces at step zero over
+    /// every start position with coordinates in `0..modulus`.
+    ///
+    /// This measures key sensitivity: a strong configuration maps a
+    /// fixed byte to many different outputs as the key varies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `modulus` is zero or the keyspace
+    /// (`modulus` to the power of the rotor count) exceeds
+    /// [`MAX_SPREAD_KEYSPACE`](Self::MAX_SPREAD_KEYSPACE).
+    pub fn output_spread(&self, input: u8, modulus: u32) -> EnigmaResult<usize> {
+        if modulus == 0 {
+            return Err(EnigmaError::InvalidConfiguration(
+                "modulus must be non-zero".into(),
+            ));
+        }
+
+        let keyspace = u64::from(modulus)
+            .checked_pow(self.rotors.len() as u32)
+            .filter(|&k| k <= Self::MAX_SPREAD_KEYSPACE)
+            .ok_or_else(|| {
+                EnigmaError::InvalidConfiguration(format!(
+                    "keyspace of {modulus}^{} positions exceeds {}",
+                    self.rotors.len(),
+                    Self::MAX_SPREAD_KEYSPACE
+                ))
+            })?;
+
+        let mut seen = [false; 256];
+        let mut state = EnigmaState::new(self.rotors.len());
+
+        for key in 0..keyspace {
+            let mut rest = key;
+            for pos in state.rotor_positions.iter_mut().rev() {
+                *pos = (rest % u64::from(modulus)) as u32;
+                rest /= u64::from(modulus);
+            }
+
+            seen[self.transform(input, &state) as usize] = true;
+        }
+
+        Ok(seen.iter().filter(|&&s| s).count())
+    }
+
+    /// Checks that decrypting `sample` reproduces it.
+    ///
+    /// The sample is enciphered from the zero state and the result is
+    /// processed again from a fresh zero state. This catches pipelines
+    /// that are not self-inverse, such as a non-involutive reflector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidConfiguration` naming the first
+    /// byte that does not round-trip, or any processing error.
+    pub fn verify_reversibility(&self, sample: &[u8]) -> EnigmaResult<()> {
+        let start = EnigmaState::new(self.rotors.len());
+
+        let ciphertext = self.process_bytes(sample, &mut start.clone())?;
+        let decrypted = self.process_bytes(&ciphertext, &mut start.clone())?;
+
+        match sample.iter().zip(&decrypted).position(|(a, b)| a != b) {
+            None => Ok(()),
+            Some(i) => Err(EnigmaError::InvalidConfiguration(format!(
+                "machine is not reversible: byte {i} ({}) decrypts to {}",
+                sample[i], decrypted[i]
+            ))),
+        }
+    }
+
+    /// Returns how many bytes remain until the given rotor advances.
+    ///
+    /// A value of `1` means the rotor moves after the next byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rotor index is out of range or the
+    /// stepping strategy cannot predict when the rotor advances.
+    pub fn bytes_until_turnover(
+        &self,
+        state: &EnigmaState,
+        rotor_index: usize,
+    ) -> EnigmaResult<u64> {
+        self.check_state(state)?;
+
+        if rotor_index >= self.rotors.len() {
+            return Err(EnigmaError::InvalidState(format!(
+                "rotor index {rotor_index} out of bounds"
+            )));
+        }
 
-        // Step state AFTER processing
         self.stepping
-            .step(state)
-            .map_err(EnigmaError::SteppingError)?;
+            .steps_until_advance(state, rotor_index)
+            .ok_or_else(|| {
+                EnigmaError::SteppingError(format!(
+                    "cannot predict when rotor {rotor_index} advances"
+                ))
+            })
+    }
 
-        Ok(value)
+    /// Exports the signal path at the given state as a Graphviz DOT graph.
+    ///
+    /// Each stage (input, plugboard, rotors, reflector, rotors in
+    /// reverse, plugboard in reverse) is drawn as a cluster, and the
+    /// edges show the effective byte mapping of every stage for the
+    /// letters `A`–`H`. The state is not stepped.
+    pub fn to_dot(&self, state: &EnigmaState) -> EnigmaResult<String> {
+        self.check_state(state)?;
+
+        let mut stages = vec!["input".to_string(), "plugboard".to_string()];
+        stages.extend((0..self.rotors.len()).map(|i| format!("rotor {i}")));
+        stages.push("reflector".to_string());
+        stages.extend(
+            (0..self.rotors.len())
+                .rev()
+                .map(|i| format!("rotor {i} (reverse)")),
+        );
+        stages.push("plugboard (reverse)".to_string());
+
+        let mut nodes = vec![BTreeSet::new(); stages.len()];
+        let mut edges = BTreeSet::new();
+
+        for symbol in DOT_SYMBOLS {
+            let mut path = vec![symbol, self.plugboard.forward(symbol, state)];
+            for rotor in &self.rotors {
+                path.push(rotor.forward(*path.last().unwrap(), state));
+            }
+            path.push(self.reflector.forward(*path.last().unwrap(), state));
+            for rotor in self.rotors.iter().rev() {
+                path.push(rotor.backward(*path.last().unwrap(), state));
+            }
+            path.push(self.plugboard.backward(*path.last().unwrap(), state));
+
+            for (stage, &value) in path.iter().enumerate() {
+                nodes[stage].insert(value);
+            }
+            for (stage, pair) in path.windows(2).enumerate() {
+                edges.insert((stage, pair[0], pair[1]));
+            }
+        }
+
+        let mut dot = String::from("digraph enigma {\n    rankdir=LR;\n    node [shape=circle];\n");
+
+        for (stage, label) in stages.iter().enumerate() {
+            let _ = writeln!(dot, "    subgraph cluster_{stage} {{");
+            let _ = writeln!(dot, "        label=\"{label}\";");
+            for &value in &nodes[stage] {
+                let _ = writeln!(
+                    dot,
+                    "        s{stage}_{value} [label=\"{}\"];",
+                    dot_label(value)
+                );
+            }
+            let _ = writeln!(dot, "    }}");
+        }
+
+        for (stage, from, to) in edges {
+            let _ = writeln!(dot, "    s{stage}_{from} -> s{}_{to};", stage + 1);
+        }
+
+        dot.push_str("}\n");
+
+        Ok(dot)
+    }
+
+    /// Encrypts a message key under the ground setting (indicator procedure).
+    ///
+    /// Each rotor position of `message_key` is serialized as a
+    /// big-endian `u32` and the bytes are enciphered from `ground`,
+    /// which is left untouched. The result is sent ahead of the
+    /// message so the receiver can recover its start positions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either state does not match the rotor count.
+    pub fn encode_indicator(
+        &self,
+        message_key: &EnigmaState,
+        ground: &EnigmaState,
+    ) -> EnigmaResult<Vec<u8>> {
+        if message_key.rotor_positions.len() != self.rotors.len() {
+            return Err(EnigmaError::InvalidState(
+                "message key position count does not match rotor count".into(),
+            ));
+        }
+
+        let plain: Vec<u8> = message_key
+            .rotor_positions
+            .iter()
+            .flat_map(|p| p.to_be_bytes())
+            .collect();
+
+        self.process_bytes(&plain, &mut ground.clone())
+    }
+
+    /// Recovers a message key from an indicator produced by
+    /// [`EnigmaMachine::encode_indicator`] with the same ground setting.
+    ///
+    /// The returned state has the decoded positions and a zero step
+    /// counter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the indicator length does not match the
+    /// rotor count or the ground state is invalid.
+    pub fn decode_indicator(
+        &self,
+        indicator: &[u8],
+        ground: &EnigmaState,
+    ) -> EnigmaResult<EnigmaState> {
+        if indicator.len() != self.rotors.len() * 4 {
+            return Err(EnigmaError::InvalidState(format!(
+                "indicator must be {} bytes for {} rotor(s)",
+                self.rotors.len() * 4,
+                self.rotors.len()
+            )));
+        }
+
+        let plain = self.process_bytes(indicator, &mut ground.clone())?;
+
+        Ok(EnigmaState {
+            rotor_positions: plain
+                .chunks_exact(4)
+                .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+                .collect(),
+            step_counter: 0,
+        })
+    }
+
+    /// Returns human-readable differences between two machine configurations.
+    ///
+    /// Components are compared by effect: each component's forward
+    /// mapping is evaluated over all 256 bytes at the zero state.
+    /// An empty vector means no difference was found.
+    pub fn diff(&self, other: &EnigmaMachine) -> Vec<String> {
+        let mut differences = Vec::new();
+
+        if self.rotors.len() != other.rotors.len() {
+            differences.push(format!(
+                "rotor count: {} vs {}",
+                self.rotors.len(),
+                other.rotors.len()
+            ));
+        }
+
+        let state = EnigmaState::new(self.rotors.len().max(other.rotors.len()));
+
+        if let Some(d) = diff_component("plugboard", &*self.plugboard, &*other.plugboard, &state) {
+            differences.push(d);
+        }
+
+        for (i, (a, b)) in self.rotors.iter().zip(&other.rotors).enumerate() {
+            if let Some(d) = diff_component(&format!("rotor {i}"), &**a, &**b, &state) {
+                differences.push(d);
+            }
+        }
+
+        if let Some(d) = diff_component("reflector", &*self.reflector, &*other.reflector, &state) {
+            differences.push(d);
+        }
+
+        let (m1, m2) = (self.stepping.modulus(), other.stepping.modulus());
+        if m1 != m2 {
+            let show = |m: Option<u32>| m.map_or_else(|| "unknown".to_string(), |m| m.to_string());
+            differences.push(format!("stepping modulus: {} vs {}", show(m1), show(m2)));
+        }
+
+        differences
     }
 
     /// Processes a slice of bytes through the Enigma pipeline.
@@ -93,4 +711,108 @@ impl EnigmaMachine {
 
         Ok(output)
     }
+
+    /// Processes a slice of bytes, also returning the rotor positions
+    /// used for each byte.
+    ///
+    /// Entry `i` of the snapshots holds the rotor positions that
+    /// enciphered byte `i`: those before the step, or after it when
+    /// stepping before processing.
+    pub fn process_bytes_with_steps(
+        &self,
+        input: &[u8],
+        state: &mut EnigmaState,
+    ) -> EnigmaResult<(Vec<u8>, Vec<Vec<u32>>)> {
+        let mut output = Vec::with_capacity(input.len());
+        let mut snapshots = Vec::with_capacity(input.len());
+
+        for &byte in input {
+            let before = state.rotor_positions.clone();
+            output.push(self.process_byte(byte, state)?);
+
+            snapshots.push(if self.step_before {
+                state.rotor_positions.clone()
+            } else {
+                before
+            });
+        }
+
+        Ok((output, snapshots))
+    }
+
+    /// Processes a slice of bytes, returning one alignment entry per
+    /// byte: the input byte, the output byte, and the rotor positions
+    /// that enciphered it.
+    ///
+    /// The outputs are exactly those of [`EnigmaMachine::process_bytes`].
+    pub fn process_with_alignment(
+        &self,
+        input: &[u8],
+        state: &mut EnigmaState,
+    ) -> EnigmaResult<Vec<(u8, u8, Vec<u32>)>> {
+        let (output, snapshots) = self.process_bytes_with_steps(input, state)?;
+
+        Ok(input
+            .iter()
+            .zip(output)
+            .zip(snapshots)
+            .map(|((&i, o), positions)| (i, o, positions))
+            .collect())
+    }
+}
+
+/// Component adapter that swaps the forward and backward directions.
+struct Inverse(Rc<dyn EnigmaComponent>);
+
+impl EnigmaComponent for Inverse {
+    fn forward(&self, input: u8, state: &EnigmaState) -> u8 {
+        self.0.backward(input, state)
+    }
+
+    fn backward(&self, input: u8, state: &EnigmaState) -> u8 {
+        self.0.forward(input, state)
+    }
+}
+
+/// Formats a byte as a DOT node label.
+fn dot_label(value: u8) -> String {
+    match value {
+        b'"' | b'\\' => format!("\\{}", value as char),
+        v if v.is_ascii_graphic() => (v as char).to_string(),
+        v => v.to_string(),
+    }
+}
+
+/// Evaluates a component's forward mapping over all bytes.
+fn component_table(component: &dyn EnigmaComponent, state: &EnigmaState) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    for (i, v) in table.iter_mut().enumerate() {
+        *v = component.forward(i as u8, state);
+    }
+    table
+}
+
+/// Describes where two components' forward mappings disagree.
+fn diff_component(
+    name: &str,
+    a: &dyn EnigmaComponent,
+    b: &dyn EnigmaComponent,
+    state: &EnigmaState,
+) -> Option<String> {
+    let (ta, tb) = (component_table(a, state), component_table(b, state));
+
+    let mismatches: Vec<String> = (0..256)
+        .filter(|&i| ta[i] != tb[i])
+        .map(|i| format!("{i} ({} vs {})", ta[i], tb[i]))
+        .collect();
+
+    if mismatches.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "{name} differs at {} byte(s): {}",
+        mismatches.len(),
+        mismatches.join(", ")
+    ))
 }
@@ -3,9 +3,13 @@
 //! The plugboard performs a fixed, bidirectional permutation of bytes
 //! before and after the rotor pipeline.
 
+#[cfg(feature = "serde")]
+use crate::hex::MappingRepr;
 use crate::{
     component::EnigmaComponent,
     error::{EnigmaError, EnigmaResult},
+    prng::SplitMix64,
+    reflector::pair_mapping,
     state::EnigmaState,
 };
 
@@ -15,6 +19,11 @@ use crate::{
 /// The permutation must be an involution (i.e. symmetric), so that
 /// forward and backward transformations are identical.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "MappingRepr", into = "MappingRepr")
+)]
 pub struct Plugboard {
     mapping: [u8; 256],
 }
@@ -24,19 +33,86 @@ impl Plugboard {
     ///
     /// The mapping must be an involution:
     /// `mapping[mapping[x]] == x` for all `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first index where symmetry breaks
+    /// and the values involved.
     pub fn new(mapping: [u8; 256]) -> EnigmaResult<Self> {
-        for i in 0..256 {
-            let j = mapping[i] as usize;
-            if mapping[j] != i as u8 {
-                return Err(EnigmaError::InvalidConfiguration(
-                    "plugboard mapping must be symmetric (involution)".into(),
-                ));
+        for (i, &j) in mapping.iter().enumerate() {
+            let k = mapping[j as usize];
+            if k != i as u8 {
+                return Err(EnigmaError::InvalidConfiguration(format!(
+                    "plugboard not symmetric at {i}: mapping[{i}]={j} but mapping[{j}]={k}"
+                )));
             }
         }
 
         Ok(Self { mapping })
     }
 
+    /// Creates a plugboard from reciprocal swap pairs, like the cables
+    /// of the historical plugboard.
+    ///
+    /// Bytes not listed map to themselves; an empty list gives the
+    /// identity plugboard.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a byte is used in more than one pair or a
+    /// pair connects a byte to itself.
+    pub fn from_pairs(pairs: &[(u8, u8)]) -> EnigmaResult<Self> {
+        Ok(Self {
+            mapping: pair_mapping(pairs, "plugboard")?,
+        })
+    }
+
+    /// Replaces the wiring with the given swap pairs.
+    ///
+    /// The new pairs are validated as in [`Plugboard::from_pairs`]; on
+    /// error the current wiring is left untouched.
+    ///
+    /// A machine holds its plugboard behind a trait object, so rewiring
+    /// is done on an owned `Plugboard` that is then installed with
+    /// [`EnigmaMachine::with_plugboard`](crate::EnigmaMachine::with_plugboard).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a byte is used in more than one pair or a
+    /// pair connects a byte to itself.
+    pub fn rewire(&mut self, pairs: &[(u8, u8)]) -> EnigmaResult<()> {
+        self.mapping = pair_mapping(pairs, "plugboard")?;
+        Ok(())
+    }
+
+    /// Creates a deterministic pseudo-random plugboard with `pairs`
+    /// disjoint reciprocal swaps.
+    ///
+    /// The bytes are shuffled with a seeded PRNG and the first
+    /// `2 * pairs` are paired up; the rest map to themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pairs` exceeds 128.
+    pub fn random(seed: u64, pairs: usize) -> EnigmaResult<Self> {
+        if pairs > 128 {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "a plugboard has at most 128 pairs (got {pairs})"
+            )));
+        }
+
+        let mut order: Vec<u8> = (0..=255).collect();
+        SplitMix64::new(seed).shuffle(&mut order);
+
+        let swaps: Vec<(u8, u8)> = order
+            .chunks_exact(2)
+            .take(pairs)
+            .map(|pair| (pair[0], pair[1]))
+            .collect();
+
+        Self::from_pairs(&swaps)
+    }
+
     /// Creates an identity plugboard (no transformation).
     pub fn identity() -> Self {
         let mut mapping = [0u8; 256];
@@ -45,6 +121,58 @@ impl Plugboard {
         }
         Self { mapping }
     }
+
+    /// Returns the active swap pairs, each once with the smaller byte
+    /// first, in ascending order.
+    ///
+    /// This is the inverse of [`Plugboard::from_pairs`].
+    pub fn pairs(&self) -> Vec<(u8, u8)> {
+        (0..=255u8)
+            .map(|a| (a, self.mapping[a as usize]))
+            .filter(|&(a, b)| a < b)
+            .collect()
+    }
+
+    /// Returns how many bytes the plugboard does not map to themselves
+    /// (twice the number of swap pairs).
+    pub fn swapped_count(&self) -> usize {
+        self.mapping
+            .iter()
+            .enumerate()
+            .filter(|&(i, &v)| v != i as u8)
+            .count()
+    }
+
+    /// Returns `true` if the plugboard leaves every byte unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.swapped_count() == 0
+    }
+
+    /// Returns `true` if both plugboards apply the same transformation.
+    ///
+    /// The mapping fully determines the effect, so this holds for
+    /// plugboards built through different constructors as long as
+    /// they map every byte identically.
+    pub fn same_effect_as(&self, other: &Plugboard) -> bool {
+        self.mapping == other.mapping
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Plugboard> for MappingRepr {
+    fn from(plugboard: Plugboard) -> Self {
+        MappingRepr::new(&plugboard.mapping)
+    }
+}
+
+/// Rejects mappings that are not involutions.
+#[cfg(feature = "serde")]
+impl TryFrom<MappingRepr> for Plugboard {
+    type Error = EnigmaError;
+
+    fn try_from(repr: MappingRepr) -> EnigmaResult<Self> {
+        Plugboard::new(repr.decode("plugboard mapping")?)
+    }
 }
 
 impl EnigmaComponent for Plugboard {
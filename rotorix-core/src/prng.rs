@@ -0,0 +1,76 @@
+//! Deterministic pseudo-random helpers.
+//!
+//! These generators drive seeded construction and passphrase key
+//! derivation. They are reproducible by design and NOT suitable for
+//! cryptographic use.
+//!
+//! Only [`mix64`] is public. Its output is stable: it will not change
+//! without a major version bump, so values derived from it (such as
+//! key sheets) stay reproducible across releases.
+
+/// Rounds of stretching applied by [`derive_seed`].
+const KDF_ROUNDS: u32 = 100_000;
+
+/// SplitMix64 generator.
+pub(crate) struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Creates a generator from a seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64-bit output.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix64(self.state)
+    }
+
+    /// Returns a value in `0..bound`.
+    ///
+    /// The modulo bias is negligible for the small bounds used here.
+    pub(crate) fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Shuffles `items` in place (Fisher–Yates).
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Applies the SplitMix64 finalizer to `z`.
+///
+/// A bijection on `u64` that spreads every input bit over the whole
+/// output, so nearby inputs give unrelated results. Like the rest of
+/// this module it is not a cryptographic hash.
+pub fn mix64(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Derives a 64-bit seed from a passphrase.
+///
+/// The passphrase is hashed with FNV-1a and then stretched through
+/// [`KDF_ROUNDS`] SplitMix64 rounds, each mixing in the running hash.
+pub(crate) fn derive_seed(passphrase: &str) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for &b in passphrase.as_bytes() {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01B3);
+    }
+
+    let mut rng = SplitMix64::new(hash);
+    for _ in 0..KDF_ROUNDS {
+        hash ^= rng.next_u64();
+        rng = SplitMix64::new(hash);
+    }
+
+    hash
+}
@@ -0,0 +1,84 @@
+//! Recording component wrapper.
+//!
+//! Wraps any component and records every transformation it performs,
+//! producing a transcript of that stage's activity for debugging and
+//! replay.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{component::EnigmaComponent, state::EnigmaState};
+
+/// Direction of a recorded transformation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Forward pass (towards the reflector).
+    Forward,
+    /// Reverse pass (back from the reflector).
+    Backward,
+}
+
+/// A single transformation recorded by `RecordingComponent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transformation {
+    /// Pass in which the transformation happened.
+    pub direction: Direction,
+    /// Input symbol.
+    pub input: u8,
+    /// Output symbol.
+    pub output: u8,
+    /// Rotor positions at the time of the transformation.
+    pub positions: Vec<u32>,
+}
+
+/// Shared buffer of recorded transformations.
+pub type Transcript = Rc<RefCell<Vec<Transformation>>>;
+
+/// Component decorator that records every transformation.
+///
+/// Outputs are always those of the inner component; recording is a
+/// side channel and never affects the pipeline.
+pub struct RecordingComponent<C> {
+    inner: C,
+    transcript: Transcript,
+}
+
+impl<C: EnigmaComponent> RecordingComponent<C> {
+    /// Wraps a component with an empty transcript.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            transcript: Transcript::default(),
+        }
+    }
+
+    /// Returns a handle to the shared transcript.
+    ///
+    /// The handle stays valid after the component has been moved
+    /// into a machine.
+    pub fn transcript(&self) -> Transcript {
+        Rc::clone(&self.transcript)
+    }
+
+    fn record(&self, direction: Direction, input: u8, output: u8, state: &EnigmaState) -> u8 {
+        self.transcript.borrow_mut().push(Transformation {
+            direction,
+            input,
+            output,
+            positions: state.rotor_positions.clone(),
+        });
+        output
+    }
+}
+
+impl<C: EnigmaComponent> EnigmaComponent for RecordingComponent<C> {
+    fn forward(&self, input: u8, state: &EnigmaState) -> u8 {
+        let output = self.inner.forward(input, state);
+        self.record(Direction::Forward, input, output, state)
+    }
+
+    fn backward(&self, input: u8, state: &EnigmaState) -> u8 {
+        let output = self.inner.backward(input, state);
+        self.record(Direction::Backward, input, output, state)
+    }
+}
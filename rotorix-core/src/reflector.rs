@@ -4,9 +4,12 @@
 //! maps each byte to another byte such that applying it twice
 //! yields the original value.
 
+#[cfg(feature = "serde")]
+use crate::hex::MappingRepr;
 use crate::{
     component::EnigmaComponent,
     error::{EnigmaError, EnigmaResult},
+    prng::SplitMix64,
     state::EnigmaState,
 };
 
@@ -15,6 +18,11 @@ use crate::{
 /// Internally, the reflector stores a fixed involutive mapping:
 /// `mapping[mapping[x]] == x`.
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "MappingRepr", into = "MappingRepr")
+)]
 pub struct Reflector {
     mapping: [u8; 256],
 }
@@ -25,16 +33,82 @@ impl Reflector {
     /// The mapping must be an involution:
     /// `mapping[mapping[x]] == x` for all `x`.
     pub fn new(mapping: [u8; 256]) -> EnigmaResult<Self> {
-        for i in 0..256 {
-            let j = mapping[i] as usize;
-            if mapping[j] != i as u8 {
-                return Err(EnigmaError::InvalidConfiguration(
-                    "reflector mapping must be symmetric (involution)".into(),
-                ));
+        Self::validate(&mapping)?;
+
+        Ok(Self { mapping })
+    }
+
+    /// Checks that a mapping is an involution without building a
+    /// reflector.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidConfiguration` naming the first
+    /// byte `x` for which `mapping[mapping[x]] != x`.
+    pub fn validate(mapping: &[u8; 256]) -> EnigmaResult<()> {
+        for (x, &y) in mapping.iter().enumerate() {
+            if mapping[y as usize] != x as u8 {
+                return Err(EnigmaError::InvalidConfiguration(format!(
+                    "reflector mapping must be symmetric (involution): {x} -> {y} -> {}",
+                    mapping[y as usize]
+                )));
             }
         }
 
-        Ok(Self { mapping })
+        Ok(())
+    }
+
+    /// Returns `true` if the mapping is an involution.
+    ///
+    /// Always holds for reflectors built through this crate; kept as a
+    /// diagnostic for configurations loaded from elsewhere.
+    pub fn is_involution(&self) -> bool {
+        Self::validate(&self.mapping).is_ok()
+    }
+
+    /// Returns the bytes this reflector maps to themselves.
+    ///
+    /// A classic Enigma reflector has none; imported wirings can use
+    /// this to detect accidental identities.
+    pub fn fixed_points(&self) -> Vec<u8> {
+        (0..=255u8)
+            .filter(|&x| self.mapping[x as usize] == x)
+            .collect()
+    }
+
+    /// Creates a reflector from swap pairs, like the field-rewirable
+    /// UKW-D.
+    ///
+    /// Each pair `(a, b)` maps `a` to `b` and `b` to `a`; bytes not
+    /// listed map to themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a byte appears in more than one pair or a
+    /// pair maps a byte to itself.
+    pub fn from_pairs(pairs: &[(u8, u8)]) -> EnigmaResult<Self> {
+        Ok(Self {
+            mapping: pair_mapping(pairs, "reflector")?,
+        })
+    }
+
+    /// Creates a deterministic pseudo-random reflector.
+    ///
+    /// All 256 bytes are shuffled with a seeded PRNG and adjacent
+    /// elements are paired, like [`Reflector::paired`] over a shuffled
+    /// order. The result is an involution without fixed points, and
+    /// the same seed always yields the same reflector.
+    pub fn random(seed: u64) -> Self {
+        let mut order: Vec<u8> = (0..=255).collect();
+        SplitMix64::new(seed).shuffle(&mut order);
+
+        let mut mapping = [0u8; 256];
+        for pair in order.chunks_exact(2) {
+            mapping[pair[0] as usize] = pair[1];
+            mapping[pair[1] as usize] = pair[0];
+        }
+
+        Self { mapping }
     }
 
     /// Creates an identity reflector.
@@ -66,6 +140,64 @@ impl Reflector {
 
         Self { mapping }
     }
+
+    /// Returns `true` if both reflectors apply the same transformation.
+    ///
+    /// The mapping fully determines the effect, so this holds for
+    /// reflectors built through different constructors as long as
+    /// they map every byte identically.
+    pub fn same_effect_as(&self, other: &Reflector) -> bool {
+        self.mapping == other.mapping
+    }
+}
+
+/// Builds the involution swapping each of `pairs`, other bytes fixed.
+///
+/// `component` names the caller in error messages.
+pub(crate) fn pair_mapping(pairs: &[(u8, u8)], component: &str) -> EnigmaResult<[u8; 256]> {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+
+    let mut used = [false; 256];
+    for &(a, b) in pairs {
+        if a == b {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "{component} pair maps byte {a} to itself"
+            )));
+        }
+
+        for x in [a, b] {
+            if std::mem::replace(&mut used[x as usize], true) {
+                return Err(EnigmaError::InvalidConfiguration(format!(
+                    "{component} byte {x} appears in more than one pair"
+                )));
+            }
+        }
+
+        mapping[a as usize] = b;
+        mapping[b as usize] = a;
+    }
+
+    Ok(mapping)
+}
+
+#[cfg(feature = "serde")]
+impl From<Reflector> for MappingRepr {
+    fn from(reflector: Reflector) -> Self {
+        MappingRepr::new(&reflector.mapping)
+    }
+}
+
+/// Rejects mappings that are not involutions.
+#[cfg(feature = "serde")]
+impl TryFrom<MappingRepr> for Reflector {
+    type Error = EnigmaError;
+
+    fn try_from(repr: MappingRepr) -> EnigmaResult<Self> {
+        Reflector::new(repr.decode("reflector mapping")?)
+    }
 }
 
 impl EnigmaComponent for Reflector {
@@ -78,3 +210,108 @@ impl EnigmaComponent for Reflector {
         self.mapping[input as usize]
     }
 }
+
+/// Reflector that switches between several involutions.
+///
+/// The active reflector is selected from the position of rotor 0
+/// modulo the number of candidates. The selection depends only on
+/// the state, so `backward` always picks the same involution as
+/// `forward` and the machine stays reversible.
+#[derive(Debug, Clone)]
+pub struct SwitchingReflector {
+    reflectors: Vec<Reflector>,
+}
+
+impl SwitchingReflector {
+    /// Creates a new `SwitchingReflector` from candidate reflectors.
+    ///
+    /// Each candidate is already guaranteed to be involutive by
+    /// `Reflector` construction.
+    pub fn new(reflectors: Vec<Reflector>) -> EnigmaResult<Self> {
+        if reflectors.is_empty() {
+            return Err(EnigmaError::InvalidConfiguration(
+                "switching reflector requires at least one reflector".into(),
+            ));
+        }
+
+        Ok(Self { reflectors })
+    }
+
+    fn select(&self, state: &EnigmaState) -> &Reflector {
+        let pos = state.rotor_positions.first().copied().unwrap_or(0) as usize;
+        &self.reflectors[pos % self.reflectors.len()]
+    }
+}
+
+impl EnigmaComponent for SwitchingReflector {
+    fn forward(&self, input: u8, state: &EnigmaState) -> u8 {
+        self.select(state).forward(input, state)
+    }
+
+    fn backward(&self, input: u8, state: &EnigmaState) -> u8 {
+        self.select(state).backward(input, state)
+    }
+}
+
+/// Reflector whose wiring rotates with the step counter.
+///
+/// At step `n` the base involution is conjugated by an offset of
+/// `n % modulus`, like a rotor's position offset: the input is shifted
+/// forward, looked up, and shifted back. A conjugated involution is
+/// still an involution, so at any fixed step the reflector is
+/// self-inverse and the machine stays reversible as long as encryption
+/// and decryption replay the same states. Bytes `>= modulus` pass
+/// through unchanged.
+#[derive(Debug, Clone)]
+pub struct MovingReflector {
+    base: [u8; 256],
+    modulus: u16,
+}
+
+impl MovingReflector {
+    /// Creates a moving reflector from a base involution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base` is not an involution, `modulus` is
+    /// not in `1..=256`, or `base` maps a byte below `modulus` to one
+    /// at or above it.
+    pub fn new(base: [u8; 256], modulus: u16) -> EnigmaResult<Self> {
+        Reflector::validate(&base)?;
+
+        if modulus == 0 || modulus > 256 {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "moving reflector modulus must be in 1..=256 (got {modulus})"
+            )));
+        }
+
+        if let Some(x) = (0..modulus).find(|&x| u16::from(base[x as usize]) >= modulus) {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "moving reflector maps {x} outside the modulus {modulus}"
+            )));
+        }
+
+        Ok(Self { base, modulus })
+    }
+}
+
+impl EnigmaComponent for MovingReflector {
+    fn forward(&self, input: u8, state: &EnigmaState) -> u8 {
+        let modulus = u64::from(self.modulus);
+        let input = u64::from(input);
+
+        if input >= modulus {
+            return input as u8;
+        }
+
+        let offset = state.step_counter % modulus;
+        let mapped = u64::from(self.base[((input + offset) % modulus) as usize]);
+
+        ((mapped + modulus - offset) % modulus) as u8
+    }
+
+    fn backward(&self, input: u8, state: &EnigmaState) -> u8 {
+        // Self-inverse at any fixed step
+        self.forward(input, state)
+    }
+}
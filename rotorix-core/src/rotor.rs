@@ -3,9 +3,13 @@
 //! A rotor performs a state-dependent, reversible transformation.
 //! The current rotor position is read from `EnigmaState`.
 
+use std::fmt;
+
 use crate::{
     component::EnigmaComponent,
     error::{EnigmaError, EnigmaResult},
+    hex,
+    prng::SplitMix64,
     state::EnigmaState,
 };
 
@@ -13,7 +17,12 @@ use crate::{
 ///
 /// The rotor uses a fixed permutation table and applies an offset
 /// derived from the rotor position stored in `EnigmaState`.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "RotorRepr", into = "RotorRepr")
+)]
 pub struct Rotor {
     /// Forward permutation table.
     forward: [u8; 256],
@@ -21,40 +30,111 @@ pub struct Rotor {
     backward: [u8; 256],
     /// Index of this rotor in the EnigmaState rotor_positions vector.
     index: usize,
+    /// Size of the symbol space the rotor operates on.
+    ///
+    /// Symbols `>= modulus` pass through unchanged.
+    modulus: u16,
+    /// Positions at which this rotor carries over to the next one.
+    notches: Vec<u32>,
+    /// Ring setting (Ringstellung): rotates the wiring against the
+    /// position, so the effective offset is `position - ring`.
+    ring: u8,
+    /// Optional human-readable name used in diagnostics.
+    label: Option<String>,
 }
 
-fn lcg_next(state: &mut u32) -> u32 {
-    // Linear Congruential Generator (deterministic)
-    *state = state.wrapping_mul(1664525).wrapping_add(1013904223);
-    *state
-}
+/// Number of symbols in a KL-7 style wiring (A–Z, 0–9).
+pub const SYMBOL_COUNT: usize = 36;
 
 impl Rotor {
     /// Creates a new `Rotor` from a permutation table and a rotor index.
     ///
     /// The permutation must be bijective.
     pub fn new(permutation: [u8; 256], index: usize) -> EnigmaResult<Self> {
+        Self::new_with_modulus(permutation, index, 256)
+    }
+
+    /// Creates a rotor over the symbol space `0..modulus`.
+    ///
+    /// Entries `0..modulus` of `permutation` must be a permutation of
+    /// `0..modulus`; the remaining entries are ignored. Position
+    /// offsets wrap at `modulus` and symbols `>= modulus` pass through
+    /// unchanged, so the rotor should be paired with a stepping
+    /// modulus of the same size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `modulus` is not in `1..=256` or an entry is
+    /// `>= modulus` or repeated.
+    pub fn new_with_modulus(
+        permutation: [u8; 256],
+        index: usize,
+        modulus: u16,
+    ) -> EnigmaResult<Self> {
+        Self::validate(&permutation, modulus)?;
+
+        let mut forward = [0u8; 256];
         let mut backward = [0u8; 256];
-        let mut seen = [false; 256];
+        for (i, (f, b)) in forward.iter_mut().zip(backward.iter_mut()).enumerate() {
+            *f = i as u8;
+            *b = i as u8;
+        }
 
-        for (i, &v) in permutation.iter().enumerate() {
-            let j = v as usize;
-            if seen[j] {
-                return Err(EnigmaError::InvalidConfiguration(
-                    "rotor permutation must be bijective".into(),
-                ));
-            }
-            seen[j] = true;
-            backward[j] = i as u8;
+        for (i, &v) in permutation[..modulus as usize].iter().enumerate() {
+            forward[i] = v;
+            backward[v as usize] = i as u8;
         }
 
         Ok(Self {
-            forward: permutation,
+            forward,
             backward,
             index,
+            modulus,
+            notches: Vec::new(),
+            ring: 0,
+            label: None,
         })
     }
 
+    /// Returns the size of the symbol space this rotor operates on.
+    pub fn modulus(&self) -> u16 {
+        self.modulus
+    }
+
+    /// Checks that entries `0..modulus` of a permutation table are a
+    /// permutation of `0..modulus` without building a rotor.
+    ///
+    /// Use a modulus of 256 to check a full byte rotor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidConfiguration` if `modulus` is not
+    /// in `1..=256`, or naming the first index whose output is outside
+    /// the modulus or was already produced by an earlier index.
+    pub fn validate(permutation: &[u8; 256], modulus: u16) -> EnigmaResult<()> {
+        if modulus == 0 || modulus > 256 {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "rotor modulus must be in 1..=256 (got {modulus})"
+            )));
+        }
+
+        let mut seen = [false; 256];
+        for (i, &v) in permutation[..modulus as usize].iter().enumerate() {
+            if u16::from(v) >= modulus {
+                return Err(EnigmaError::InvalidConfiguration(format!(
+                    "rotor entry {v} at index {i} is outside the modulus {modulus}"
+                )));
+            }
+            if std::mem::replace(&mut seen[v as usize], true) {
+                return Err(EnigmaError::InvalidConfiguration(format!(
+                    "rotor permutation must be bijective (index {i} repeats output {v})"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Creates an identity rotor (no permutation).
     pub fn identity(index: usize) -> Self {
         let mut perm = [0u8; 256];
@@ -66,6 +146,10 @@ impl Rotor {
             forward: perm,
             backward: perm,
             index,
+            modulus: 256,
+            notches: Vec::new(),
+            ring: 0,
+            label: None,
         }
     }
 
@@ -97,16 +181,44 @@ impl Rotor {
             forward,
             backward,
             index,
+            modulus: 256,
+            notches: Vec::new(),
+            ring: 0,
+            label: None,
         }
     }
 
+    /// Creates `count` shifted rotors with indices `0..count`, rotor
+    /// `i` using `shifts[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `shifts` does not have exactly `count`
+    /// entries.
+    pub fn shifted_series(count: usize, shifts: &[u8]) -> EnigmaResult<Vec<Rotor>> {
+        if shifts.len() != count {
+            return Err(EnigmaError::InvalidConfiguration(format!(
+                "expected {count} shift(s), got {}",
+                shifts.len()
+            )));
+        }
+
+        Ok(shifts
+            .iter()
+            .enumerate()
+            .map(|(i, &shift)| Rotor::shifted(i, shift))
+            .collect())
+    }
+
     /// Creates a seed-based permutation rotor.
     ///
     /// The permutation is fully deterministic and derived from:
     /// - the provided seed
     /// - the rotor index
     ///
-    /// This rotor provides a real, non-identity wiring.
+    /// The wiring is a Fisher–Yates shuffle driven by SplitMix64, so
+    /// the same `(index, seed)` always yields the same rotor on every
+    /// platform. This rotor provides a real, non-identity wiring.
     pub fn from_seed(index: usize, seed: u64) -> Self {
         let mut forward = [0u8; 256];
         let mut backward = [0u8; 256];
@@ -116,14 +228,12 @@ impl Rotor {
             *v = i as u8;
         }
 
-        // Derive a deterministic PRNG state
-        let mut rng = (seed as u32).wrapping_add((index as u32).wrapping_mul(0x9E3779B9));
+        // SplitMix64 stream keyed on (seed, index); the index is mixed
+        // first so adjacent indices do not share overlapping streams
+        let key = SplitMix64::new(index as u64).next_u64();
+        let mut rng = SplitMix64::new(seed ^ key);
 
-        // Fisher–Yates shuffle
-        for i in (1..256).rev() {
-            let j = (lcg_next(&mut rng) % (i as u32 + 1)) as usize;
-            forward.swap(i, j);
-        }
+        rng.shuffle(&mut forward);
 
         // Build inverse permutation
         for (i, &v) in forward.iter().enumerate() {
@@ -134,22 +244,340 @@ impl Rotor {
             forward,
             backward,
             index,
+            modulus: 256,
+            notches: Vec::new(),
+            ring: 0,
+            label: None,
         }
     }
+
+    /// Creates a seed-based rotor with the given notch positions.
+    ///
+    /// Equivalent to [`Rotor::from_seed`] followed by
+    /// [`Rotor::with_notches`].
+    pub fn from_seed_with_notches(index: usize, seed: u64, notches: Vec<u32>) -> Self {
+        Self::from_seed(index, seed).with_notches(notches)
+    }
+
+    /// Creates a rotor over a 36-symbol alphabet (KL-7 style).
+    ///
+    /// `wiring[i]` is the symbol that symbol `i` maps to, with symbols
+    /// numbered `0..36`. Position offsets wrap at 36, so the rotor
+    /// should be paired with a stepping modulus of 36. Bytes outside
+    /// the symbol space pass through unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the wiring is not a permutation of `0..36`,
+    /// as checked by [`Rotor::validate`].
+    pub fn from_symbol_wiring(wiring: &[u8; SYMBOL_COUNT], index: usize) -> EnigmaResult<Self> {
+        let mut table = [0u8; 256];
+        for (i, v) in table.iter_mut().enumerate() {
+            *v = i as u8;
+        }
+        table[..SYMBOL_COUNT].copy_from_slice(wiring);
+
+        Self::new_with_modulus(table, index, SYMBOL_COUNT as u16)
+    }
+
+    /// Creates a rotor from its forward table written as 512 hex digits.
+    ///
+    /// Each pair of digits is one table entry, in order. Upper- and
+    /// lowercase digits are accepted and surrounding whitespace is
+    /// ignored. The table is then checked like [`Rotor::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the length or the position of the first
+    /// non-hex character, or if the table is not bijective.
+    pub fn from_wiring_str(s: &str, index: usize) -> EnigmaResult<Self> {
+        Self::new(hex::decode_table(s, "rotor wiring")?, index)
+    }
+
+    /// Returns the forward table as 512 uppercase hex digits, the format
+    /// read by [`Rotor::from_wiring_str`].
+    pub fn to_wiring_str(&self) -> String {
+        hex::encode_table(&self.forward)
+    }
+
+    /// Returns a rotor with the forward and backward wiring swapped.
+    ///
+    /// The index, symbol space and notches are kept, so at any state
+    /// the inverse's `backward` equals this rotor's `forward`.
+    pub fn inverse(&self) -> Rotor {
+        Rotor {
+            forward: self.backward,
+            backward: self.forward,
+            ..self.clone()
+        }
+    }
+
+    /// Collapses `self` followed by `other` into a single rotor.
+    ///
+    /// The composed forward table is `other.forward[self.forward[x]]`
+    /// and its backward table is the inverse.
+    ///
+    /// The result is only equivalent to running the two rotors in
+    /// sequence when both are at position zero: each rotor applies its
+    /// own position offset, which a single table cannot reproduce. The
+    /// composed rotor has modulus 256 and no notches.
+    pub fn compose(&self, other: &Rotor, index: usize) -> Rotor {
+        let mut forward = [0u8; 256];
+        let mut backward = [0u8; 256];
+
+        for (x, f) in forward.iter_mut().enumerate() {
+            *f = other.forward[self.forward[x] as usize];
+            backward[*f as usize] = x as u8;
+        }
+
+        Rotor {
+            forward,
+            backward,
+            index,
+            modulus: 256,
+            notches: Vec::new(),
+            ring: 0,
+            label: None,
+        }
+    }
+
+    /// Sets a human-readable label (e.g. `"left"`) for diagnostics.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Returns the label of this rotor, if any.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Sets the ring setting (Ringstellung) of this rotor.
+    ///
+    /// The ring rotates the wiring relative to the position, so the
+    /// effective offset is `position - ring` (modulo the rotor
+    /// modulus). The default ring is 0.
+    pub fn with_ring(mut self, ring: u8) -> Self {
+        self.ring = ring;
+        self
+    }
+
+    /// Sets the notch positions of this rotor.
+    ///
+    /// A notch marks a position at which the rotor carries over to the
+    /// next one (turnover). Rotors are built without notches.
+    pub fn with_notches(mut self, notches: Vec<u32>) -> Self {
+        self.notches = notches;
+        self
+    }
+
+    /// Returns the notch positions of this rotor.
+    pub fn notches(&self) -> &[u32] {
+        &self.notches
+    }
+
+    /// Returns the positions at which this rotor turns its neighbor
+    /// over.
+    ///
+    /// These are the notches; rotors like the historical VI–VIII have
+    /// two.
+    pub fn turnover_positions(&self) -> &[u32] {
+        &self.notches
+    }
+
+    /// Returns `true` if the rotor's position in `state` is one of its
+    /// notches.
+    ///
+    /// Returns `false` if the state has no position for this rotor.
+    pub fn is_at_notch(&self, state: &EnigmaState) -> bool {
+        self.position(state)
+            .is_ok_and(|pos| self.notches.contains(&pos))
+    }
+
+    /// Returns the symbol shown for this rotor's position in `state`
+    /// (the position modulo the rotor modulus).
+    ///
+    /// Like the transformation itself, a state without a position for
+    /// this rotor reads as position 0; use
+    /// [`Rotor::try_position_symbol`] to detect that case.
+    pub fn position_symbol(&self, state: &EnigmaState) -> u8 {
+        self.try_position_symbol(state).unwrap_or(0)
+    }
+
+    /// Returns the symbol shown for this rotor's position in `state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidState` if the state has no position
+    /// for this rotor's index.
+    pub fn try_position_symbol(&self, state: &EnigmaState) -> EnigmaResult<u8> {
+        let pos = self.position(state)?;
+
+        Ok((pos % u32::from(self.modulus)) as u8)
+    }
+
+    /// Advances only this rotor's position in `state` by one, wrapping
+    /// at the rotor modulus.
+    ///
+    /// Other positions and the step counter are left untouched, which
+    /// makes this a building block for custom stepping strategies and
+    /// manual key setting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EnigmaError::InvalidState` if the state has no position
+    /// for this rotor's index.
+    pub fn advance(&self, state: &mut EnigmaState) -> EnigmaResult<()> {
+        let pos = self.position(state)?;
+        let modulus = u32::from(self.modulus);
+        state.rotor_positions[self.index] = (pos % modulus + 1) % modulus;
+
+        Ok(())
+    }
+
+    /// Looks up a symbol in `table` at the given rotor position.
+    fn map(&self, table: &[u8; 256], input: u8, pos: u32) -> u8 {
+        let modulus = u32::from(self.modulus);
+        let input = u32::from(input);
+
+        if input >= modulus {
+            return input as u8;
+        }
+
+        let ring = u32::from(self.ring) % modulus;
+        let offset = (pos % modulus + modulus - ring) % modulus;
+        let shifted = (input + offset) % modulus;
+        let mapped = u32::from(table[shifted as usize]);
+
+        ((mapped + modulus - offset) % modulus) as u8
+    }
+
+    /// Returns how many bytes the forward table maps to themselves.
+    ///
+    /// A random permutation has about one fixed point on average;
+    /// many more suggest a weak wiring.
+    pub fn fixed_point_count(&self) -> usize {
+        self.forward
+            .iter()
+            .enumerate()
+            .filter(|&(i, &v)| v == i as u8)
+            .count()
+    }
+
+    /// Returns `true` if the forward table maps any byte to itself.
+    pub fn has_fixed_points(&self) -> bool {
+        self.fixed_point_count() > 0
+    }
+
+    /// Returns the lengths of the cycles of the forward permutation.
+    ///
+    /// Cycles are listed in order of their smallest element. Long
+    /// cycles indicate a well-mixed wiring, while many short cycles
+    /// (or fixed points, i.e. cycles of length 1) indicate weakness.
+    pub fn cycle_structure(&self) -> Vec<usize> {
+        let mut visited = [false; 256];
+        let mut cycles = Vec::new();
+
+        for start in 0..256 {
+            if visited[start] {
+                continue;
+            }
+
+            let mut len = 0;
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                i = self.forward[i] as usize;
+                len += 1;
+            }
+            cycles.push(len);
+        }
+
+        cycles
+    }
+}
+
+/// Shows the settings; the 256-entry tables are left out to keep the
+/// output readable.
+impl fmt::Debug for Rotor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Rotor")
+            .field("label", &self.label)
+            .field("index", &self.index)
+            .field("modulus", &self.modulus)
+            .field("ring", &self.ring)
+            .field("notches", &self.notches)
+            .finish_non_exhaustive()
+    }
 }
 
 impl EnigmaComponent for Rotor {
     fn forward(&self, input: u8, state: &EnigmaState) -> u8 {
         let pos = self.position(state).unwrap_or(0);
-        let shifted = input.wrapping_add(pos as u8);
-        let mapped = self.forward[shifted as usize];
-        mapped.wrapping_sub(pos as u8)
+        self.map(&self.forward, input, pos)
     }
 
     fn backward(&self, input: u8, state: &EnigmaState) -> u8 {
         let pos = self.position(state).unwrap_or(0);
-        let shifted = input.wrapping_add(pos as u8);
-        let mapped = self.backward[shifted as usize];
-        mapped.wrapping_sub(pos as u8)
+        self.map(&self.backward, input, pos)
+    }
+}
+
+/// Serialized form of a [`Rotor`].
+///
+/// The forward table is stored as 512 hex digits; `backward` is
+/// rebuilt (and bijectivity checked) on deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct RotorRepr {
+    forward: String,
+    index: usize,
+    #[serde(default = "RotorRepr::default_modulus")]
+    modulus: u16,
+    #[serde(default)]
+    ring: u8,
+    #[serde(default)]
+    notches: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl RotorRepr {
+    fn default_modulus() -> u16 {
+        256
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Rotor> for RotorRepr {
+    fn from(rotor: Rotor) -> Self {
+        Self {
+            forward: rotor.to_wiring_str(),
+            index: rotor.index,
+            modulus: rotor.modulus,
+            ring: rotor.ring,
+            notches: rotor.notches,
+            label: rotor.label,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RotorRepr> for Rotor {
+    type Error = EnigmaError;
+
+    fn try_from(repr: RotorRepr) -> EnigmaResult<Self> {
+        let table = hex::decode_table(&repr.forward, "rotor wiring")?;
+
+        let rotor = Rotor::new_with_modulus(table, repr.index, repr.modulus)?
+            .with_ring(repr.ring)
+            .with_notches(repr.notches);
+
+        Ok(Rotor {
+            label: repr.label,
+            ..rotor
+        })
     }
 }
@@ -4,11 +4,22 @@
 //! All state is external to the components and can be safely cloned,
 //! snapshotted, and restored.
 
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::{fs, io, path::Path};
+
+use crate::stepping::SteppingStrategy;
+
 /// Represents the mutable state of an Enigma transformation session.
 ///
 /// The state is intentionally kept simple and explicit to guarantee
 /// deterministic behavior and ease of testing.
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
 pub struct EnigmaState {
     /// Current positions of the rotors.
     ///
@@ -32,6 +43,27 @@ impl EnigmaState {
         }
     }
 
+    /// Creates a state whose rotor positions are derived from `seed`.
+    ///
+    /// Rotor `i` starts at byte `i` of the little-endian seed, reduced
+    /// modulo `modulus` so every position is valid for the stepping
+    /// strategy. Rotors beyond the eighth start at 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn from_seed(rotor_count: usize, seed: u64, modulus: u32) -> Self {
+        assert!(modulus > 0, "stepping modulus must be greater than zero");
+
+        let bytes = seed.to_le_bytes();
+        let mut state = Self::new(rotor_count);
+        for (pos, &byte) in state.rotor_positions.iter_mut().zip(&bytes) {
+            *pos = u32::from(byte) % modulus;
+        }
+
+        state
+    }
+
     /// Resets all rotor positions and the step counter to zero.
     pub fn reset(&mut self) {
         for pos in &mut self.rotor_positions {
@@ -39,4 +71,162 @@ impl EnigmaState {
         }
         self.step_counter = 0;
     }
+
+    /// Sets the rotor positions after checking them against the
+    /// stepping modulus.
+    ///
+    /// The state is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `positions` does not have one entry per
+    /// rotor, or if any position is not below `modulus`.
+    pub fn set_positions(&mut self, positions: &[u32], modulus: u32) -> Result<(), String> {
+        if positions.len() != self.rotor_positions.len() {
+            return Err(format!(
+                "expected {} position(s), got {}",
+                self.rotor_positions.len(),
+                positions.len()
+            ));
+        }
+
+        if let Some((i, &pos)) = positions.iter().enumerate().find(|&(_, &p)| p >= modulus) {
+            return Err(format!(
+                "position {pos} of rotor {i} is out of range (modulus {modulus})"
+            ));
+        }
+
+        self.rotor_positions.copy_from_slice(positions);
+        Ok(())
+    }
+
+    /// Returns a copy of the state to return to later with
+    /// [`EnigmaState::restore`].
+    pub fn snapshot(&self) -> EnigmaState {
+        self.clone()
+    }
+
+    /// Copies the positions and step counter of `snapshot` back into
+    /// this state.
+    ///
+    /// The existing position buffer is reused when it is large
+    /// enough.
+    pub fn restore(&mut self, snapshot: &EnigmaState) {
+        self.rotor_positions.clone_from(&snapshot.rotor_positions);
+        self.step_counter = snapshot.step_counter;
+    }
+
+    /// Advances the state `n` times with the given stepping strategy.
+    ///
+    /// Useful for a key-setting offset applied before the first
+    /// symbol. Stops at the first error, leaving the steps taken so
+    /// far applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`SteppingStrategy::step`].
+    pub fn advance_by(&mut self, stepping: &dyn SteppingStrategy, n: u64) -> Result<(), String> {
+        for _ in 0..n {
+            stepping.step(self)?;
+        }
+
+        Ok(())
+    }
+
+    /// Undoes `n` steps with the given stepping strategy.
+    ///
+    /// The counterpart of [`EnigmaState::advance_by`]. Stops at the
+    /// first error, leaving the steps undone so far applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`SteppingStrategy::step_back`], e.g.
+    /// when the strategy cannot run backwards.
+    pub fn rewind(&mut self, stepping: &dyn SteppingStrategy, n: u64) -> Result<(), String> {
+        for _ in 0..n {
+            stepping.step_back(self)?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders the rotor positions as letters, `A` for position 0
+    /// through `Z` for position 25.
+    ///
+    /// Returns `None` if any position is outside `0..26`.
+    pub fn rotor_letters(&self) -> Option<String> {
+        self.rotor_positions
+            .iter()
+            .map(|&p| {
+                u8::try_from(p)
+                    .ok()
+                    .filter(|&p| p < 26)
+                    .map(|p| (b'A' + p) as char)
+            })
+            .collect()
+    }
+}
+
+/// Formats the state as `positions=[0, 13, 5] step=42`.
+impl fmt::Display for EnigmaState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "positions={:?} step={}",
+            self.rotor_positions, self.step_counter
+        )
+    }
+}
+
+/// JSON helpers for persisting a session, in the form
+/// `{"rotor_positions":[1,2,3],"step_counter":42}`.
+#[cfg(feature = "serde")]
+impl EnigmaState {
+    /// Serializes the state as compact JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a state always serializes to JSON")
+    }
+
+    /// Parses a state written by [`EnigmaState::to_json`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error for malformed JSON, a missing or
+    /// unknown field, or an out-of-range number.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    /// Writes the state as JSON to `path`.
+    ///
+    /// The JSON goes to a temporary file next to `path` which is then
+    /// renamed over it, so an interrupted save never leaves a
+    /// truncated state behind.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from writing or renaming the file.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+
+        fs::write(&tmp, self.to_json())?;
+        fs::rename(&tmp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&tmp);
+        })
+    }
+
+    /// Reads a state written by [`EnigmaState::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from reading the file, or an
+    /// [`io::ErrorKind::InvalidData`] error if it does not hold a
+    /// valid state.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+
+        Self::from_json(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
 }
@@ -3,7 +3,22 @@
 //! A stepping strategy defines how the Enigma state evolves after
 //! each processed symbol.
 
-use crate::state::EnigmaState;
+use crate::{
+    error::{EnigmaError, EnigmaResult},
+    prng::SplitMix64,
+    rotor::Rotor,
+    state::EnigmaState,
+};
+
+/// Description of what moved during one step.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct StepReport {
+    /// Indices of the rotors that advanced, in ascending order.
+    pub advanced: Vec<usize>,
+
+    /// `true` if a rotor's movement was carried into another rotor.
+    pub carry: bool,
+}
 
 /// Strategy that controls how the Enigma state advances.
 ///
@@ -19,25 +34,499 @@ pub trait SteppingStrategy {
     /// Implementations may return an error if the state cannot be
     /// advanced (e.g. invalid configuration).
     fn step(&self, state: &mut EnigmaState) -> Result<(), String>;
+
+    /// Advances the Enigma state by one step for the given input byte.
+    ///
+    /// The machine calls this instead of [`step`](Self::step) when
+    /// processing a byte, so strategies can make stepping depend on
+    /// the symbol. The default ignores the input and calls `step`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `step`.
+    fn step_with_input(&self, state: &mut EnigmaState, _input: u8) -> Result<(), String> {
+        self.step(state)
+    }
+
+    /// Advances the Enigma state by one step and reports what moved.
+    ///
+    /// The default implementation calls [`step`](Self::step) and
+    /// compares rotor positions: rotors whose position changed are
+    /// reported as advanced, and a carry is assumed whenever more
+    /// than one rotor moved. Strategies that know better should
+    /// override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from `step`.
+    fn step_detailed(&self, state: &mut EnigmaState) -> Result<StepReport, String> {
+        let before = state.rotor_positions.clone();
+        self.step(state)?;
+
+        let advanced: Vec<usize> = before
+            .iter()
+            .zip(&state.rotor_positions)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(i, _)| i)
+            .collect();
+
+        Ok(StepReport {
+            carry: advanced.len() > 1,
+            advanced,
+        })
+    }
+
+    /// Undoes one [`step`](Self::step).
+    ///
+    /// Strategies that can run backwards override this; the default
+    /// reports that reverse stepping is not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the strategy cannot step back, or if the
+    /// state has no earlier step (`step_counter` is 0).
+    fn step_back(&self, _state: &mut EnigmaState) -> Result<(), String> {
+        Err("reverse stepping not supported".into())
+    }
+
+    /// Checks the configuration for a machine with `rotor_count`
+    /// rotors.
+    ///
+    /// Called by [`EnigmaMachine::new`](crate::EnigmaMachine::new) so
+    /// that a configuration that can never step is rejected at build
+    /// time instead of mid-stream. The default accepts everything.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem.
+    fn validate(&self, _rotor_count: usize) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Returns the modulus applied to rotor positions, if any.
+    ///
+    /// Used for diagnostics only. Strategies without a single
+    /// well-defined modulus keep the default `None`.
+    fn modulus(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns how many steps remain until the given rotor advances.
+    ///
+    /// A value of `1` means the rotor moves on the next step.
+    /// Strategies that cannot predict this (or rotors that never
+    /// advance) return `None`.
+    fn steps_until_advance(&self, _state: &EnigmaState, _rotor_index: usize) -> Option<u64> {
+        None
+    }
 }
 
 /// A simple linear stepping strategy.
 ///
 /// Each call increments the first rotor position and propagates
 /// overflow to the next rotors (odometer-style).
+///
+/// Rotors can be marked inactive to keep them at a fixed position
+/// (e.g. the M4 thin rotor). Inactive rotors are skipped by the
+/// odometer and carries propagate to the next active rotor.
+///
+/// Rotors share a single modulus by default; a mixed-radix odometer
+/// is built with [`LinearStepping::with_moduli`].
 pub struct LinearStepping {
     /// Modulus applied to each rotor position.
+    ///
+    /// Unused when per-rotor moduli are set:
+    /// [`LinearStepping::with_moduli`] leaves it at `0`, and the
+    /// effective moduli are read from [`LinearStepping::moduli`].
     pub modulus: u32,
+
+    /// Per-rotor moduli; empty means every rotor wraps at `modulus`.
+    moduli: Vec<u32>,
+
+    /// Per-rotor stepping flags; empty means every rotor is active.
+    active: Vec<bool>,
 }
 
 impl LinearStepping {
     /// Creates a new `LinearStepping` strategy.
+    ///
+    /// The modulus is not validated here; a zero modulus fails at
+    /// `step` time.
+    #[deprecated(note = "use try_new")]
     pub fn new(modulus: u32) -> Self {
-        Self { modulus }
+        Self::unchecked(modulus)
+    }
+
+    /// Builds the strategy without validating the modulus.
+    pub(crate) fn unchecked(modulus: u32) -> Self {
+        Self {
+            modulus,
+            moduli: Vec::new(),
+            active: Vec::new(),
+        }
+    }
+
+    /// Creates a new `LinearStepping` strategy, validating the modulus.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `modulus` is zero.
+    pub fn try_new(modulus: u32) -> EnigmaResult<Self> {
+        if modulus == 0 {
+            return Err(EnigmaError::InvalidConfiguration(
+                "modulus must be greater than zero".into(),
+            ));
+        }
+
+        Ok(Self::unchecked(modulus))
+    }
+
+    /// Creates a mixed-radix `LinearStepping` strategy, rotor `i`
+    /// wrapping at `moduli[i]`.
+    ///
+    /// The moduli are not validated here; a zero entry or a machine
+    /// with a different number of rotors is rejected by
+    /// [`SteppingStrategy::validate`] when the machine is built.
+    pub fn with_moduli(moduli: Vec<u32>) -> Self {
+        Self {
+            modulus: 0,
+            moduli,
+            active: Vec::new(),
+        }
+    }
+
+    /// Predicts whether the next odometer step from `state` carries
+    /// out of the first rotor, i.e. whether that rotor wraps past
+    /// `modulus - 1`.
+    ///
+    /// The state is not modified. Returns `false` for an empty state
+    /// or a zero modulus, where stepping fails instead. Inactive rotor
+    /// flags are not considered.
+    pub fn carry_on_step(state: &EnigmaState, modulus: u32) -> bool {
+        modulus > 0
+            && state
+                .rotor_positions
+                .first()
+                .is_some_and(|&pos| pos.saturating_add(1) >= modulus)
+    }
+
+    /// Sets the per-rotor stepping flags.
+    ///
+    /// Rotors flagged `false` keep their position while the others
+    /// step normally. A non-empty list must have one flag per rotor,
+    /// which [`SteppingStrategy::validate`] checks when the machine is
+    /// built.
+    pub fn with_active(mut self, active: Vec<bool>) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Returns the per-rotor moduli, empty if every rotor uses
+    /// `modulus`.
+    pub fn moduli(&self) -> &[u32] {
+        &self.moduli
+    }
+
+    /// Returns the per-rotor stepping flags.
+    pub fn active(&self) -> &[bool] {
+        &self.active
+    }
+
+    fn is_active(&self, index: usize) -> bool {
+        self.active.get(index).copied().unwrap_or(true)
+    }
+
+    fn modulus_at(&self, index: usize) -> u32 {
+        self.moduli.get(index).copied().unwrap_or(self.modulus)
+    }
+
+    /// Checks the moduli and stepping flags against a state with
+    /// `rotors` rotors.
+    fn check(&self, rotors: usize) -> Result<(), String> {
+        if !self.active.is_empty() && self.active.len() != rotors {
+            return Err(format!(
+                "stepping has {} active flag(s) but the state has {} rotor(s)",
+                self.active.len(),
+                rotors
+            ));
+        }
+
+        if self.moduli.is_empty() {
+            if self.modulus == 0 {
+                return Err("modulus must be greater than zero".into());
+            }
+            return Ok(());
+        }
+
+        if self.moduli.len() != rotors {
+            return Err(format!(
+                "stepping has {} moduli but the state has {} rotor(s)",
+                self.moduli.len(),
+                rotors
+            ));
+        }
+
+        if self.moduli.contains(&0) {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        Ok(())
+    }
+
+    /// Runs one odometer step, recording what moved in `report` if
+    /// one is given.
+    fn advance(
+        &self,
+        state: &mut EnigmaState,
+        mut report: Option<&mut StepReport>,
+    ) -> Result<(), String> {
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        self.check(state.rotor_positions.len())?;
+
+        // Increment step counter
+        state.step_counter += 1;
+
+        // Odometer-style stepping (inactive rotors are held fixed)
+        for (i, pos) in state.rotor_positions.iter_mut().enumerate() {
+            if !self.is_active(i) {
+                continue;
+            }
+
+            if let Some(report) = report.as_deref_mut() {
+                report.advanced.push(i);
+            }
+
+            if *pos < self.modulus_at(i) - 1 {
+                *pos += 1;
+                break;
+            }
+            *pos = 0;
+
+            if let Some(report) = report.as_deref_mut() {
+                report.carry = true;
+            }
+        }
+
+        Ok(())
     }
 }
 
 impl SteppingStrategy for LinearStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        self.advance(state, None)
+    }
+
+    /// Decrements the first active rotor, borrowing from the next
+    /// active rotor when it is at 0, and decrements `step_counter`.
+    /// For a state with positions inside the moduli, `step` followed
+    /// by `step_back` restores the original state.
+    fn step_back(&self, state: &mut EnigmaState) -> Result<(), String> {
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        self.check(state.rotor_positions.len())?;
+
+        if state.step_counter == 0 {
+            return Err("cannot step back before the first step".into());
+        }
+
+        state.step_counter -= 1;
+
+        for (i, pos) in state.rotor_positions.iter_mut().enumerate() {
+            if !self.is_active(i) {
+                continue;
+            }
+
+            if *pos > 0 {
+                *pos -= 1;
+                break;
+            }
+            *pos = self.modulus_at(i) - 1;
+        }
+
+        Ok(())
+    }
+
+    /// Reports every rotor the odometer moved; `carry` is set when a
+    /// rotor rolled over, even if it was the last one.
+    fn step_detailed(&self, state: &mut EnigmaState) -> Result<StepReport, String> {
+        let mut report = StepReport::default();
+        self.advance(state, Some(&mut report))?;
+
+        Ok(report)
+    }
+
+    fn modulus(&self) -> Option<u32> {
+        match self.moduli.split_first() {
+            None => Some(self.modulus),
+            Some((&first, rest)) => rest.iter().all(|&m| m == first).then_some(first),
+        }
+    }
+
+    fn steps_until_advance(&self, state: &EnigmaState, rotor_index: usize) -> Option<u64> {
+        if rotor_index >= state.rotor_positions.len()
+            || !self.is_active(rotor_index)
+            || self.check(state.rotor_positions.len()).is_err()
+        {
+            return None;
+        }
+
+        // The active rotors below `rotor_index` form a mixed-radix
+        // number; the rotor advances when that number rolls over.
+        let mut span: u64 = 1;
+        let mut value: u64 = 0;
+
+        for (i, &pos) in state.rotor_positions[..rotor_index].iter().enumerate() {
+            if self.is_active(i) {
+                value = value.saturating_add(u64::from(pos).saturating_mul(span));
+                span = span.saturating_mul(u64::from(self.modulus_at(i)));
+            }
+        }
+
+        Some(span.saturating_sub(value).max(1))
+    }
+
+    fn validate(&self, rotor_count: usize) -> Result<(), String> {
+        self.check(rotor_count)
+    }
+}
+
+/// Advances `positions` as an odometer in which every rotor wraps at
+/// `modulus`.
+///
+/// `modulus` must be non-zero.
+fn odometer_step(positions: &mut [u32], modulus: u32) {
+    for pos in positions {
+        if *pos < modulus - 1 {
+            *pos += 1;
+            break;
+        }
+        *pos = 0;
+    }
+}
+
+/// Notch-driven stepping, as on the historical Enigma.
+///
+/// Rotor 0 (the fast rotor) advances on every step. Rotor `i + 1`
+/// advances only when rotor `i` advances from one of its notch
+/// positions; unlike [`LinearStepping`], wrapping past `modulus - 1`
+/// does not carry by itself.
+pub struct NotchStepping {
+    /// Modulus applied to each rotor position.
+    pub modulus: u32,
+
+    /// Notch positions per rotor, in rotor order.
+    ///
+    /// Rotors without an entry have no notches and never carry.
+    pub notches: Vec<Vec<u32>>,
+}
+
+impl NotchStepping {
+    /// Creates a notch-driven stepping strategy from per-rotor notch
+    /// sets.
+    pub fn new(modulus: u32, notches: Vec<Vec<u32>>) -> Self {
+        Self { modulus, notches }
+    }
+
+    /// Creates a notch-driven stepping strategy taking the notch sets
+    /// from the given rotors, `rotors[i]` being rotor `i`.
+    pub fn from_rotors(modulus: u32, rotors: &[Rotor]) -> Self {
+        Self::new(
+            modulus,
+            rotors
+                .iter()
+                .map(|rotor| rotor.notches().to_vec())
+                .collect(),
+        )
+    }
+
+    fn is_at_notch(&self, index: usize, position: u32) -> bool {
+        self.notches
+            .get(index)
+            .is_some_and(|notches| notches.contains(&position))
+    }
+}
+
+impl SteppingStrategy for NotchStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        if self.modulus == 0 {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        state.step_counter += 1;
+
+        // Each rotor moves only if its faster neighbor moves off a notch
+        for i in 0..state.rotor_positions.len() {
+            let position = state.rotor_positions[i];
+            state.rotor_positions[i] = (position % self.modulus + 1) % self.modulus;
+
+            if !self.is_at_notch(i, position) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn modulus(&self) -> Option<u32> {
+        Some(self.modulus)
+    }
+
+    fn validate(&self, _rotor_count: usize) -> Result<(), String> {
+        if self.modulus == 0 {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Enigma stepping including the double-stepping anomaly.
+///
+/// Each rotor `i > 0` has a pawl that engages when rotor `i - 1` is at
+/// one of its notches. An engaged pawl pushes both rotor `i` and the
+/// notch ring of rotor `i - 1`, so both advance. Rotor 0 advances on
+/// every step regardless.
+///
+/// With three rotors this is the historical behavior: when the middle
+/// rotor reaches its notch (having been carried there by the fast
+/// rotor), it steps again on the very next key press together with the
+/// left rotor, e.g. `ADU → ADV → AEW → BFX` for rotors I–II–III.
+///
+/// All pawls are checked against the positions before the step.
+pub struct DoubleStepping {
+    /// Modulus applied to each rotor position.
+    pub modulus: u32,
+
+    /// Notch positions per rotor, in rotor order.
+    ///
+    /// Rotors without an entry have no notches.
+    pub notches: Vec<Vec<u32>>,
+}
+
+impl DoubleStepping {
+    /// Creates a double-stepping strategy from per-rotor notch sets.
+    pub fn new(modulus: u32, notches: Vec<Vec<u32>>) -> Self {
+        Self { modulus, notches }
+    }
+
+    fn is_at_notch(&self, index: usize, position: u32) -> bool {
+        self.notches
+            .get(index)
+            .is_some_and(|notches| notches.contains(&position))
+    }
+}
+
+impl SteppingStrategy for DoubleStepping {
     fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
         if self.modulus == 0 {
             return Err("modulus must be greater than zero".into());
@@ -47,18 +536,252 @@ impl SteppingStrategy for LinearStepping {
             return Err("no rotors defined in state".into());
         }
 
-        // Increment step counter
         state.step_counter += 1;
 
-        // Odometer-style stepping
+        let count = state.rotor_positions.len();
+        let mut advance = vec![false; count];
+        advance[0] = true;
+
+        for i in 1..count {
+            if self.is_at_notch(i - 1, state.rotor_positions[i - 1]) {
+                advance[i] = true;
+                advance[i - 1] = true;
+            }
+        }
+
+        for (pos, advance) in state.rotor_positions.iter_mut().zip(advance) {
+            if advance {
+                *pos = (*pos % self.modulus + 1) % self.modulus;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn modulus(&self) -> Option<u32> {
+        Some(self.modulus)
+    }
+
+    fn validate(&self, _rotor_count: usize) -> Result<(), String> {
+        if self.modulus == 0 {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Odometer stepping that advances only on every `gap`-th symbol.
+///
+/// `step_counter` is incremented on every call; the rotor positions
+/// stay put for `gap - 1` calls and then perform one odometer advance,
+/// as [`LinearStepping`] would.
+pub struct GappedStepping {
+    /// Modulus applied to each rotor position.
+    pub modulus: u32,
+
+    /// Number of symbols per rotor advance.
+    pub gap: u64,
+}
+
+impl GappedStepping {
+    /// Creates a new `GappedStepping` strategy.
+    ///
+    /// Neither value is validated here; a zero modulus or gap fails on
+    /// the first `step`.
+    pub fn new(modulus: u32, gap: u64) -> Self {
+        Self { modulus, gap }
+    }
+}
+
+impl SteppingStrategy for GappedStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        if self.gap == 0 {
+            return Err("gap must be greater than zero".into());
+        }
+
+        if self.modulus == 0 {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        state.step_counter += 1;
+
+        if state.step_counter.is_multiple_of(self.gap) {
+            odometer_step(&mut state.rotor_positions, self.modulus);
+        }
+
+        Ok(())
+    }
+
+    fn modulus(&self) -> Option<u32> {
+        Some(self.modulus)
+    }
+
+    fn validate(&self, _rotor_count: usize) -> Result<(), String> {
+        if self.gap == 0 {
+            return Err("gap must be greater than zero".into());
+        }
+
+        if self.modulus == 0 {
+            return Err("modulus must be greater than zero".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Stepping strategy that runs several strategies in sequence.
+///
+/// Each call to `step` calls the inner strategies in order on the same
+/// state and stops at the first error. Every inner strategy sees the
+/// state left by the previous one, including its `step_counter`
+/// increment, so chaining two odometers advances the counter by two.
+pub struct ChainedStepping(pub Vec<Box<dyn SteppingStrategy>>);
+
+impl SteppingStrategy for ChainedStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        for stepping in &self.0 {
+            stepping.step(state)?;
+        }
+
+        Ok(())
+    }
+
+    fn step_with_input(&self, state: &mut EnigmaState, input: u8) -> Result<(), String> {
+        for stepping in &self.0 {
+            stepping.step_with_input(state, input)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self, rotor_count: usize) -> Result<(), String> {
+        self.0
+            .iter()
+            .try_for_each(|stepping| stepping.validate(rotor_count))
+    }
+}
+
+/// Odometer stepping with a pseudo-random advance per symbol.
+///
+/// The first rotor advances by an amount in `1..modulus` derived from
+/// `seed` and the state's `step_counter`, carrying into the next rotor
+/// when it wraps. The advance is a pure function of the state, so
+/// encryption and decryption from the same start state stay in sync.
+///
+/// The generator is NOT suitable for cryptographic use.
+pub struct RandomStepping {
+    /// Modulus applied to each rotor position.
+    pub modulus: u32,
+
+    /// Seed mixed into every advance.
+    pub seed: u64,
+}
+
+impl RandomStepping {
+    /// Creates a new `RandomStepping` strategy.
+    ///
+    /// The modulus is not validated here; a modulus below 2 fails at
+    /// `step` time.
+    pub fn new(modulus: u32, seed: u64) -> Self {
+        Self { modulus, seed }
+    }
+
+    /// Returns the advance applied at the given step counter.
+    fn advance(&self, step_counter: u64) -> u32 {
+        let key = SplitMix64::new(step_counter).next_u64();
+        let span = u64::from(self.modulus - 1);
+
+        1 + (SplitMix64::new(self.seed ^ key).below(span) as u32)
+    }
+}
+
+impl SteppingStrategy for RandomStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        if self.modulus < 2 {
+            return Err("modulus must be at least 2".into());
+        }
+
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        let mut carry = self.advance(state.step_counter);
+        state.step_counter += 1;
+
         for pos in &mut state.rotor_positions {
-            *pos += 1;
-            if *pos < self.modulus {
+            let next = u64::from(*pos) + u64::from(carry);
+            *pos = (next % u64::from(self.modulus)) as u32;
+            if next < u64::from(self.modulus) {
                 break;
             }
-            *pos = 0;
+            carry = 1;
+        }
+
+        Ok(())
+    }
+
+    fn modulus(&self) -> Option<u32> {
+        Some(self.modulus)
+    }
+
+    fn validate(&self, _rotor_count: usize) -> Result<(), String> {
+        if self.modulus < 2 {
+            return Err("modulus must be at least 2".into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Number of positions of a classic A–Z rotor.
+const ALPHABET_SIZE: u32 = 26;
+
+/// Odometer stepping over the 26 letters of a classic Enigma.
+///
+/// Rotor positions wrap at 26. When processing input, only ASCII
+/// letters advance the rotors; any other byte (spaces, digits,
+/// punctuation) still increments `step_counter` but leaves the
+/// positions unchanged, like text typed around the keyboard. Called
+/// without an input, [`step`](SteppingStrategy::step) always advances.
+///
+/// The decision is made on the byte fed to the machine, so decryption
+/// stays in sync only if the pipeline maps letters to letters and
+/// leaves other bytes as non-letters.
+#[derive(Default)]
+pub struct AlphabetStepping;
+
+impl AlphabetStepping {
+    /// Creates a new `AlphabetStepping` strategy.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SteppingStrategy for AlphabetStepping {
+    fn step(&self, state: &mut EnigmaState) -> Result<(), String> {
+        self.step_with_input(state, b'A')
+    }
+
+    fn step_with_input(&self, state: &mut EnigmaState, input: u8) -> Result<(), String> {
+        if state.rotor_positions.is_empty() {
+            return Err("no rotors defined in state".into());
+        }
+
+        state.step_counter += 1;
+
+        if input.is_ascii_alphabetic() {
+            odometer_step(&mut state.rotor_positions, ALPHABET_SIZE);
         }
 
         Ok(())
     }
+
+    fn modulus(&self) -> Option<u32> {
+        Some(ALPHABET_SIZE)
+    }
 }
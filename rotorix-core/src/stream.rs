@@ -0,0 +1,43 @@
+//! Stateful streaming facade.
+//!
+//! `StreamCipher` owns a machine together with its state so data can
+//! be fed in arbitrary chunks. Chunk boundaries never affect output.
+
+use crate::{error::EnigmaResult, machine::EnigmaMachine, state::EnigmaState};
+
+/// A machine bundled with the state it advances across calls.
+pub struct StreamCipher {
+    machine: EnigmaMachine,
+    state: EnigmaState,
+}
+
+impl StreamCipher {
+    /// Creates a stream starting at `state`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state does not match the machine.
+    pub fn new(machine: EnigmaMachine, mut state: EnigmaState) -> EnigmaResult<Self> {
+        machine.prepare_state(&mut state)?;
+
+        Ok(Self { machine, state })
+    }
+
+    /// Processes the next chunk of the stream.
+    ///
+    /// Feeding data in one call or split over many yields the same
+    /// output.
+    pub fn update(&mut self, chunk: &[u8]) -> EnigmaResult<Vec<u8>> {
+        self.machine.process_bytes(chunk, &mut self.state)
+    }
+
+    /// Returns the current state of the stream.
+    pub fn state(&self) -> &EnigmaState {
+        &self.state
+    }
+
+    /// Consumes the stream, returning the machine and its state.
+    pub fn into_parts(self) -> (EnigmaMachine, EnigmaState) {
+        (self.machine, self.state)
+    }
+}
@@ -0,0 +1,23 @@
+//! Reproducible input for tests and benchmarks.
+//!
+//! Available with the `test-support` feature so tests and benches
+//! share one generator instead of each pulling in their own.
+
+use crate::prng::SplitMix64;
+
+/// Returns `len` pseudo-random bytes determined entirely by `seed`.
+///
+/// Uses the same generator as the crate's seeded construction. Not
+/// suitable for cryptographic use.
+pub fn deterministic_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut rng = SplitMix64::new(seed);
+    let mut bytes = Vec::with_capacity(len);
+
+    while bytes.len() < len {
+        let word = rng.next_u64().to_le_bytes();
+        let take = word.len().min(len - bytes.len());
+        bytes.extend_from_slice(&word[..take]);
+    }
+
+    bytes
+}
@@ -0,0 +1,86 @@
+use rotorix_core::{
+    EnigmaComponent, EnigmaMachine, EnigmaState, LinearStepping, Plugboard, Reflector, Rotor,
+    analyze_distribution,
+};
+
+fn encrypt_zeros(
+    rotors: Vec<Box<dyn EnigmaComponent>>,
+    reflector: Box<dyn EnigmaComponent>,
+) -> Vec<u8> {
+    let count = rotors.len();
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        rotors,
+        reflector,
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap();
+
+    machine
+        .process_bytes(&[0u8; 4096], &mut EnigmaState::new(count))
+        .unwrap()
+}
+
+#[test]
+fn seeded_machine_is_more_uniform_than_identity() {
+    let identity = encrypt_zeros(
+        vec![Box::new(Rotor::identity(0))],
+        Box::new(Reflector::identity()),
+    );
+    let seeded = encrypt_zeros(
+        vec![
+            Box::new(Rotor::from_seed(0, 11)),
+            Box::new(Rotor::from_seed(1, 11)),
+            Box::new(Rotor::from_seed(2, 11)),
+        ],
+        Box::new(Reflector::paired()),
+    );
+
+    let identity_stats = analyze_distribution(&identity);
+    let seeded_stats = analyze_distribution(&seeded);
+
+    // The identity machine outputs only zeros
+    assert_eq!(identity_stats.frequencies[0], 4096);
+    assert_eq!(identity_stats.total, 4096);
+    assert!((identity_stats.chi_square - 4096.0 * 255.0).abs() < 1e-6);
+
+    assert!(seeded_stats.chi_square < identity_stats.chi_square / 100.0);
+}
+
+#[test]
+fn empty_input_has_zero_chi_square() {
+    let stats = analyze_distribution(&[]);
+    assert_eq!(stats.total, 0);
+    assert_eq!(stats.chi_square, 0.0);
+}
+
+#[test]
+fn seeded_machine_spreads_a_byte_over_more_outputs() {
+    let machine = |rotors: Vec<Box<dyn EnigmaComponent>>| {
+        EnigmaMachine::new(
+            Box::new(Plugboard::identity()),
+            rotors,
+            Box::new(Reflector::paired()),
+            Box::new(LinearStepping::try_new(256).unwrap()),
+        )
+        .unwrap()
+    };
+
+    let identity = machine(vec![Box::new(Rotor::identity(0))]);
+    let seeded = machine(vec![
+        Box::new(Rotor::from_seed(0, 11)),
+        Box::new(Rotor::from_seed(1, 11)),
+    ]);
+
+    assert_eq!(identity.output_spread(b'A', 256).unwrap(), 1);
+    assert!(seeded.output_spread(b'A', 256).unwrap() > 200);
+
+    // 256^4 start positions is beyond the bound
+    let wide = machine(
+        (0..4)
+            .map(|i| Box::new(Rotor::from_seed(i, 11)) as _)
+            .collect(),
+    );
+    assert!(wide.output_spread(b'A', 256).is_err());
+    assert!(seeded.output_spread(b'A', 0).is_err());
+}
@@ -0,0 +1,91 @@
+use rotorix_core::{
+    EnigmaComponent, EnigmaError, EnigmaMachineBuilder, EnigmaState, Reflector, Rotor,
+};
+
+#[test]
+fn build_with_state_matches_rotor_count_and_positions() {
+    let (machine, mut state) = EnigmaMachineBuilder::new()
+        .rotor(Box::new(Rotor::from_seed(0, 5)))
+        .rotor(Box::new(Rotor::from_seed(1, 5)))
+        .rotor(Box::new(Rotor::from_seed(2, 5)))
+        .reflector(Box::new(Reflector::paired()))
+        .positions(vec![3, 1, 4])
+        .build_with_state()
+        .unwrap();
+
+    assert_eq!(state.rotor_positions, vec![3, 1, 4]);
+    assert_eq!(state.step_counter, 0);
+
+    // The returned state is directly usable with the machine
+    assert!(machine.process_bytes(b"BUILDER", &mut state).is_ok());
+}
+
+#[test]
+fn build_with_state_defaults_to_zero_positions() {
+    let (_, state) = EnigmaMachineBuilder::new()
+        .rotor(Box::new(Rotor::identity(0)))
+        .rotor(Box::new(Rotor::identity(1)))
+        .reflector(Box::new(Reflector::paired()))
+        .build_with_state()
+        .unwrap();
+
+    assert_eq!(state, EnigmaState::new(2));
+}
+
+#[test]
+fn mismatched_state_is_rejected() {
+    let result = EnigmaMachineBuilder::new()
+        .rotor(Box::new(Rotor::identity(0)))
+        .reflector(Box::new(Reflector::paired()))
+        .positions(vec![0, 0])
+        .build_with_state();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn missing_reflector_is_rejected() {
+    let result = EnigmaMachineBuilder::new()
+        .rotor(Box::new(Rotor::identity(0)))
+        .build();
+
+    assert!(result.is_err());
+}
+
+/// Maps every byte to its successor: a bijection but not an involution.
+struct Successor;
+
+impl EnigmaComponent for Successor {
+    fn forward(&self, input: u8, _state: &EnigmaState) -> u8 {
+        input.wrapping_add(1)
+    }
+
+    fn backward(&self, input: u8, _state: &EnigmaState) -> u8 {
+        input.wrapping_sub(1)
+    }
+}
+
+#[test]
+fn validate_reports_all_problems() {
+    let builder = EnigmaMachineBuilder::new().reflector(Box::new(Successor));
+
+    let message = match builder.validate() {
+        Err(EnigmaError::InvalidConfiguration(message)) => message,
+        other => panic!("unexpected result: {other:?}"),
+    };
+
+    assert!(message.starts_with("2 problem(s)"));
+    assert!(message.contains("at least one rotor is required"));
+    assert!(message.contains("reflector is not an involution (byte 0)"));
+}
+
+#[test]
+fn validate_accepts_valid_configuration() {
+    let builder = EnigmaMachineBuilder::new()
+        .rotor(Box::new(Rotor::from_seed(0, 5)))
+        .reflector(Box::new(Reflector::paired()))
+        .positions(vec![7]);
+
+    assert!(builder.validate().is_ok());
+    assert!(builder.build().is_ok());
+}
@@ -0,0 +1,55 @@
+use rotorix_core::{
+    Cascade, CascadeState, EnigmaComponent, EnigmaMachine, EnigmaState, LinearStepping, Plugboard,
+    Reflector, Rotor,
+};
+
+fn stage(seed: u64, rotors: usize) -> EnigmaMachine {
+    let rotors: Vec<Box<dyn EnigmaComponent>> = (0..rotors)
+        .map(|i| Box::new(Rotor::from_seed(i, seed)) as Box<dyn EnigmaComponent>)
+        .collect();
+
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        rotors,
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+fn start_states() -> CascadeState {
+    let mut first = EnigmaState::new(2);
+    first.rotor_positions = vec![5, 9];
+    let mut second = EnigmaState::new(3);
+    second.rotor_positions = vec![200, 1, 77];
+
+    CascadeState::new(vec![first, second])
+}
+
+#[test]
+fn two_stage_cascade_roundtrip_with_independent_states() {
+    let cascade = Cascade::new(vec![stage(1, 2), stage(2, 3)]).unwrap();
+
+    let plaintext = b"LAYERED MACHINES";
+    let mut enc_state = start_states();
+    let ciphertext = cascade.process_bytes(plaintext, &mut enc_state).unwrap();
+
+    // Each stage advanced its own state once per byte
+    assert!(enc_state.states.iter().all(|s| s.step_counter == 16));
+    assert_eq!(enc_state.states[0].rotor_positions, vec![21, 9]);
+    assert_eq!(enc_state.states[1].rotor_positions, vec![216, 1, 77]);
+
+    let decrypted = cascade
+        .process_bytes_reverse(&ciphertext, &mut start_states())
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn cascade_rejects_mismatched_state_count() {
+    let cascade = Cascade::new(vec![stage(1, 1), stage(2, 1)]).unwrap();
+    let mut state = CascadeState::new(vec![EnigmaState::new(1)]);
+
+    assert!(cascade.process_bytes(b"X", &mut state).is_err());
+    assert!(Cascade::new(Vec::new()).is_err());
+}
@@ -1,4 +1,7 @@
-use rotorix_core::{EnigmaMachine, EnigmaState, LinearStepping, Plugboard, Reflector, Rotor};
+use rotorix_core::{
+    EnigmaError, EnigmaMachine, EnigmaState, LinearStepping, Plugboard, Reflector, Rotor,
+    StateLengthPolicy,
+};
 
 #[test]
 fn roundtrip_identity_pipeline() {
@@ -9,7 +12,7 @@ fn roundtrip_identity_pipeline() {
 
     let reflector = Box::new(Reflector::identity());
 
-    let stepping = Box::new(LinearStepping::new(256));
+    let stepping = Box::new(LinearStepping::try_new(256).unwrap());
 
     // --- Machine ---
     let machine = EnigmaMachine::new(plugboard, rotors, reflector, stepping)
@@ -38,7 +41,7 @@ fn stepping_advances_state() {
     let plugboard = Box::new(Plugboard::identity());
     let rotor = Box::new(Rotor::identity(0));
     let reflector = Box::new(Reflector::identity());
-    let stepping = Box::new(LinearStepping::new(10));
+    let stepping = Box::new(LinearStepping::try_new(10).unwrap());
 
     let machine = EnigmaMachine::new(plugboard, vec![rotor], reflector, stepping).unwrap();
 
@@ -57,7 +60,7 @@ fn invalid_state_is_rejected() {
     let plugboard = Box::new(Plugboard::identity());
     let rotor = Box::new(Rotor::identity(0));
     let reflector = Box::new(Reflector::identity());
-    let stepping = Box::new(LinearStepping::new(256));
+    let stepping = Box::new(LinearStepping::try_new(256).unwrap());
 
     let machine = EnigmaMachine::new(plugboard, vec![rotor], reflector, stepping).unwrap();
 
@@ -68,3 +71,499 @@ fn invalid_state_is_rejected() {
 
     assert!(result.is_err());
 }
+
+fn machine_with_plugboard(plugboard: Plugboard) -> EnigmaMachine {
+    let rotors: Vec<Box<dyn rotorix_core::EnigmaComponent>> = vec![
+        Box::new(Rotor::from_seed(0, 42)),
+        Box::new(Rotor::from_seed(1, 42)),
+    ];
+
+    EnigmaMachine::new(
+        Box::new(plugboard),
+        rotors,
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn diff_reports_only_plugboard_difference() {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+    mapping.swap(65, 66);
+
+    let a = machine_with_plugboard(Plugboard::identity());
+    let b = machine_with_plugboard(Plugboard::new(mapping).unwrap());
+
+    assert!(a.diff(&a).is_empty());
+
+    let differences = a.diff(&b);
+    assert_eq!(differences.len(), 1);
+    assert_eq!(
+        differences[0],
+        "plugboard differs at 2 byte(s): 65 (65 vs 66), 66 (66 vs 65)"
+    );
+}
+
+fn seeded_rotors() -> Vec<Box<dyn rotorix_core::EnigmaComponent>> {
+    vec![
+        Box::new(Rotor::from_seed(0, 7)),
+        Box::new(Rotor::from_seed(1, 7)),
+    ]
+}
+
+#[test]
+fn strict_constructor_accepts_paired_reflector() {
+    let machine = EnigmaMachine::new_strict(
+        Box::new(Plugboard::identity()),
+        seeded_rotors(),
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    );
+
+    assert!(machine.is_ok());
+}
+
+#[test]
+fn strict_constructor_rejects_identity_reflector() {
+    let result = EnigmaMachine::new_strict(
+        Box::new(Plugboard::identity()),
+        seeded_rotors(),
+        Box::new(Reflector::identity()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    );
+
+    let err = result.err().expect("identity reflector must be rejected");
+    assert!(err.to_string().contains("256 byte(s) to themselves"));
+}
+
+fn one_way_machine() -> EnigmaMachine {
+    // A rotor as the turning stage is a permutation but not an
+    // involution, so the machine is not self-inverse.
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        seeded_rotors(),
+        Box::new(Rotor::from_seed(0, 1234)),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn inverse_undoes_one_way_machine() {
+    let machine = one_way_machine();
+    let inverse = machine.inverse().unwrap();
+
+    let plaintext = b"ONE WAY PIPELINE";
+    let ciphertext = machine
+        .process_bytes(plaintext, &mut EnigmaState::new(2))
+        .unwrap();
+
+    // The original machine cannot decrypt its own output
+    let replayed = machine
+        .process_bytes(&ciphertext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_ne!(replayed, plaintext);
+
+    let decrypted = inverse
+        .process_bytes(&ciphertext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn inverse_of_reflector_machine_is_equivalent() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+    let inverse = machine.inverse().unwrap();
+
+    assert!(machine.diff(&inverse).is_empty());
+}
+
+#[test]
+fn avalanche_is_limited_to_the_flipped_byte() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+    let start = EnigmaState::new(2);
+    let input = b"AVALANCHE SAMPLE";
+
+    for bit in [0, 13, 127] {
+        let ratio = machine.avalanche(input, &start, bit).unwrap();
+
+        // At least one bit changes, at most the 8 bits of one byte
+        assert!(ratio > 0.0);
+        assert!(ratio <= 8.0 / (input.len() * 8) as f64);
+    }
+
+    assert!(machine.avalanche(input, &start, 128).is_err());
+}
+
+#[test]
+fn too_long_state_is_rejected_by_default() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+
+    let mut state = EnigmaState::new(3);
+    assert!(machine.process_byte(0x41, &mut state).is_err());
+}
+
+#[test]
+fn too_long_state_is_truncated_under_policy() {
+    let machine = machine_with_plugboard(Plugboard::identity())
+        .with_state_policy(StateLengthPolicy::Truncate);
+
+    let mut long_state = EnigmaState::new(3);
+    long_state.rotor_positions[2] = 9;
+    let mut exact_state = EnigmaState::new(2);
+
+    let a = machine.process_bytes(b"TRUNCATE", &mut long_state).unwrap();
+    let b = machine
+        .process_bytes(b"TRUNCATE", &mut exact_state)
+        .unwrap();
+
+    assert_eq!(a, b);
+    assert_eq!(long_state, exact_state);
+
+    // Too-short states are never accepted
+    let mut short_state = EnigmaState::new(1);
+    assert!(machine.process_byte(0x41, &mut short_state).is_err());
+}
+
+#[test]
+fn bytes_until_turnover_follows_lower_rotor() {
+    let rotors: Vec<Box<dyn rotorix_core::EnigmaComponent>> =
+        vec![Box::new(Rotor::identity(0)), Box::new(Rotor::identity(1))];
+
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        rotors,
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(10).unwrap()),
+    )
+    .unwrap();
+
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions[0] = 7;
+
+    assert_eq!(machine.bytes_until_turnover(&state, 0).unwrap(), 1);
+    assert_eq!(machine.bytes_until_turnover(&state, 1).unwrap(), 3);
+
+    // Processing the predicted number of bytes moves rotor 1
+    machine.process_bytes(b"AB", &mut state).unwrap();
+    assert_eq!(state.rotor_positions[1], 0);
+    assert_eq!(machine.bytes_until_turnover(&state, 1).unwrap(), 1);
+
+    machine.process_byte(b'C', &mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![0, 1]);
+    assert_eq!(machine.bytes_until_turnover(&state, 1).unwrap(), 10);
+
+    assert!(machine.bytes_until_turnover(&state, 2).is_err());
+}
+
+#[test]
+fn to_dot_describes_signal_path() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+    let dot = machine.to_dot(&EnigmaState::new(2)).unwrap();
+
+    assert!(dot.starts_with("digraph enigma {"));
+    assert!(dot.trim_end().ends_with('}'));
+    assert_eq!(dot.matches('{').count(), dot.matches('}').count());
+
+    for label in [
+        "label=\"input\"",
+        "label=\"plugboard\"",
+        "label=\"rotor 0\"",
+        "label=\"rotor 1\"",
+        "label=\"reflector\"",
+        "label=\"rotor 1 (reverse)\"",
+        "label=\"plugboard (reverse)\"",
+        "s0_65 [label=\"A\"]",
+        "s0_72 [label=\"H\"]",
+    ] {
+        assert!(dot.contains(label), "missing {label}");
+    }
+
+    // Identity plugboard: A maps to A in the first stage
+    assert!(dot.contains("s0_65 -> s1_65;"));
+
+    assert!(machine.to_dot(&EnigmaState::new(1)).is_err());
+}
+
+#[test]
+fn skip_matches_processing_discarded_bytes() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+
+    let mut skipped = EnigmaState::new(2);
+    machine.skip(&mut skipped, 5).unwrap();
+
+    let mut processed = EnigmaState::new(2);
+    machine.process_bytes(b"12345", &mut processed).unwrap();
+
+    assert_eq!(skipped, processed);
+}
+
+#[test]
+fn step_snapshots_advance_like_an_odometer() {
+    let rotors: Vec<Box<dyn rotorix_core::EnigmaComponent>> = vec![
+        Box::new(Rotor::from_seed(0, 1)),
+        Box::new(Rotor::from_seed(1, 1)),
+    ];
+
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        rotors,
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(3).unwrap()),
+    )
+    .unwrap();
+
+    let input = b"CLASSROOM";
+    let (output, snapshots) = machine
+        .process_bytes_with_steps(input, &mut EnigmaState::new(2))
+        .unwrap();
+
+    assert_eq!(
+        snapshots,
+        vec![
+            vec![0, 0],
+            vec![1, 0],
+            vec![2, 0],
+            vec![0, 1],
+            vec![1, 1],
+            vec![2, 1],
+            vec![0, 2],
+            vec![1, 2],
+            vec![2, 2],
+        ]
+    );
+
+    let expected = machine
+        .process_bytes(input, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn otp_layer_roundtrips_and_depends_on_key() {
+    let plaintext = b"ONE TIME PAD LAYER";
+
+    let encrypt = |key: Vec<u8>| {
+        machine_with_plugboard(Plugboard::identity())
+            .with_otp(key)
+            .unwrap()
+            .process_bytes(plaintext, &mut EnigmaState::new(2))
+            .unwrap()
+    };
+
+    let ciphertext = encrypt(b"SECRET".to_vec());
+    assert_ne!(ciphertext, encrypt(b"ANOTHER".to_vec()));
+
+    let plain_machine = machine_with_plugboard(Plugboard::identity())
+        .process_bytes(plaintext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_ne!(ciphertext, plain_machine);
+
+    let decrypted = machine_with_plugboard(Plugboard::identity())
+        .with_otp(b"SECRET".to_vec())
+        .unwrap()
+        .process_bytes(&ciphertext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+
+    assert!(
+        machine_with_plugboard(Plugboard::identity())
+            .with_otp(Vec::new())
+            .is_err()
+    );
+}
+
+#[test]
+fn indicator_roundtrip_recovers_message_key() {
+    let machine = machine_with_plugboard(Plugboard::identity());
+
+    let mut ground = EnigmaState::new(2);
+    ground.rotor_positions = vec![17, 201];
+
+    let mut message_key = EnigmaState::new(2);
+    message_key.rotor_positions = vec![42, 7];
+
+    let indicator = machine.encode_indicator(&message_key, &ground).unwrap();
+    assert_eq!(indicator.len(), 8);
+    assert_ne!(indicator, [0, 0, 0, 42, 0, 0, 0, 7]);
+
+    // The ground setting is not consumed
+    assert_eq!(ground.rotor_positions, vec![17, 201]);
+
+    let decoded = machine.decode_indicator(&indicator, &ground).unwrap();
+    assert_eq!(decoded, message_key);
+
+    assert!(machine.decode_indicator(&indicator[..7], &ground).is_err());
+}
+
+#[test]
+fn rotor_letters_render_only_letter_positions() {
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions = vec![0, 1, 25];
+    assert_eq!(state.rotor_letters().as_deref(), Some("ABZ"));
+
+    state.rotor_positions = vec![0, 26, 2];
+    assert_eq!(state.rotor_letters(), None);
+}
+
+#[test]
+fn passphrase_derivation_is_deterministic() {
+    let (a, a_state) = EnigmaMachine::from_passphrase("correct horse battery staple", 3);
+    let (b, b_state) = EnigmaMachine::from_passphrase("correct horse battery staple", 3);
+    let (c, c_state) = EnigmaMachine::from_passphrase("correct horse battery stapler", 3);
+
+    assert_eq!(a_state, b_state);
+    assert!(a.diff(&b).is_empty());
+    assert_eq!(a_state.rotor_positions.len(), 3);
+
+    let message = b"ATTACK AT DAWN";
+    let from_a = a.process_bytes(message, &mut a_state.clone()).unwrap();
+    let from_b = b.process_bytes(message, &mut b_state.clone()).unwrap();
+    let from_c = c.process_bytes(message, &mut c_state.clone()).unwrap();
+
+    assert_eq!(from_a, from_b);
+    assert_ne!(from_a, from_c);
+    assert!(!a.diff(&c).is_empty());
+
+    // Reciprocal like any reflector machine
+    let back = a.process_bytes(&from_a, &mut a_state.clone()).unwrap();
+    assert_eq!(back, message);
+}
+
+#[test]
+fn alignment_matches_plain_pipeline() {
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        seeded_rotors(),
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(4).unwrap()),
+    )
+    .unwrap();
+
+    let input = b"BILINGUAL TABLE";
+    let alignment = machine
+        .process_with_alignment(input, &mut EnigmaState::new(2))
+        .unwrap();
+    let expected = machine
+        .process_bytes(input, &mut EnigmaState::new(2))
+        .unwrap();
+
+    assert_eq!(alignment.len(), input.len());
+    for (i, (plain, cipher, positions)) in alignment.iter().enumerate() {
+        assert_eq!(*plain, input[i]);
+        assert_eq!(*cipher, expected[i]);
+        assert_eq!(positions.len(), 2);
+    }
+    assert_eq!(alignment[5].2, vec![1, 1]);
+}
+
+#[test]
+fn reversibility_check_catches_non_involutive_turning_stage() {
+    let sample = b"REVERSIBILITY SAMPLE";
+
+    let reciprocal = machine_with_plugboard(Plugboard::identity());
+    assert!(reciprocal.verify_reversibility(sample).is_ok());
+
+    let err = one_way_machine().verify_reversibility(sample).unwrap_err();
+    assert!(err.to_string().contains("not reversible"), "{err}");
+
+    assert!(Reflector::paired().is_involution());
+    assert!(Reflector::random(3).is_involution());
+}
+
+#[test]
+fn with_plugboard_installs_rewired_plugboard() {
+    let mut plugboard = Plugboard::from_pairs(&[(b'A', b'B')]).unwrap();
+    let machine = machine_with_plugboard(plugboard.clone());
+    let before = machine
+        .process_bytes(b"ABC", &mut EnigmaState::new(2))
+        .unwrap();
+
+    plugboard.rewire(&[(b'A', b'C')]).unwrap();
+    let machine = machine.with_plugboard(Box::new(plugboard.clone()));
+    let after = machine
+        .process_bytes(b"ABC", &mut EnigmaState::new(2))
+        .unwrap();
+
+    assert_ne!(before, after);
+    assert_eq!(
+        after,
+        machine_with_plugboard(plugboard)
+            .process_bytes(b"ABC", &mut EnigmaState::new(2))
+            .unwrap()
+    );
+}
+
+fn single_rotor_machine() -> EnigmaMachine {
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::from_seed(0, 7))],
+        Box::new(Reflector::random(3)),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn step_before_uses_the_advanced_position() {
+    let post = single_rotor_machine();
+    let pre = single_rotor_machine().with_step_before(true);
+
+    let mut post_state = EnigmaState::new(1);
+    let mut pre_state = EnigmaState::new(1);
+    let post_out = post.process_byte(b'A', &mut post_state).unwrap();
+    let pre_out = pre.process_byte(b'A', &mut pre_state).unwrap();
+
+    assert_ne!(pre_out, post_out);
+    assert_eq!(
+        pre_out,
+        post.compile_table(&pre_state).unwrap()[b'A' as usize]
+    );
+    assert_eq!(pre_state.rotor_positions, post_state.rotor_positions);
+
+    // Both modes stay self-inverse
+    let ciphertext = pre
+        .process_bytes(b"HELLO", &mut EnigmaState::new(1))
+        .unwrap();
+    let plaintext = pre
+        .process_bytes(&ciphertext, &mut EnigmaState::new(1))
+        .unwrap();
+    assert_eq!(plaintext, b"HELLO");
+
+    let (_, snapshots) = pre
+        .process_bytes_with_steps(b"AB", &mut EnigmaState::new(1))
+        .unwrap();
+    assert_eq!(snapshots, vec![vec![1], vec![2]]);
+}
+
+#[test]
+#[allow(deprecated)]
+fn zero_modulus_is_rejected_at_build_time() {
+    let result = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::identity(0))],
+        Box::new(Reflector::identity()),
+        Box::new(LinearStepping::new(0)),
+    );
+
+    assert!(matches!(
+        result,
+        Err(EnigmaError::InvalidConfiguration(msg)) if msg.contains("modulus")
+    ));
+}
+
+#[test]
+fn moduli_must_match_rotor_count_at_build_time() {
+    let result = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::identity(0))],
+        Box::new(Reflector::identity()),
+        Box::new(LinearStepping::with_moduli(vec![26, 26])),
+    );
+
+    assert!(matches!(result, Err(EnigmaError::InvalidConfiguration(_))));
+}
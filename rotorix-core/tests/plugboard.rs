@@ -0,0 +1,143 @@
+use rotorix_core::{EnigmaComponent, EnigmaState, Plugboard};
+
+fn identity_mapping() -> [u8; 256] {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+    mapping
+}
+
+#[test]
+fn differently_built_plugboards_have_same_effect() {
+    let mut a = identity_mapping();
+    a.swap(1, 2);
+    a.swap(10, 20);
+
+    // Same swaps, applied in the opposite order
+    let mut b = identity_mapping();
+    b.swap(20, 10);
+    b.swap(2, 1);
+
+    let a = Plugboard::new(a).unwrap();
+    let b = Plugboard::new(b).unwrap();
+
+    assert!(a.same_effect_as(&b));
+    assert!(!a.same_effect_as(&Plugboard::identity()));
+    assert!(
+        Plugboard::new(identity_mapping())
+            .unwrap()
+            .same_effect_as(&Plugboard::identity())
+    );
+}
+
+#[test]
+fn from_pairs_applies_reciprocal_swaps() {
+    let mut mapping = identity_mapping();
+    mapping.swap(b'A' as usize, b'B' as usize);
+    mapping.swap(10, 200);
+
+    let plugboard = Plugboard::from_pairs(&[(b'A', b'B'), (200, 10)]).unwrap();
+    assert!(plugboard.same_effect_as(&Plugboard::new(mapping).unwrap()));
+
+    // No pairs: identity
+    assert!(
+        Plugboard::from_pairs(&[])
+            .unwrap()
+            .same_effect_as(&Plugboard::identity())
+    );
+}
+
+#[test]
+fn from_pairs_rejects_overlapping_pairs() {
+    let err = Plugboard::from_pairs(&[(1, 2), (3, 1)]).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("byte 1 appears in more than one pair"),
+        "{err}"
+    );
+
+    assert!(Plugboard::from_pairs(&[(4, 4)]).is_err());
+}
+
+#[test]
+fn random_plugboard_swaps_exactly_the_requested_pairs() {
+    let state = EnigmaState::new(0);
+
+    for (seed, pairs) in [(1, 0), (2, 10), (3, 13), (4, 128)] {
+        let plugboard = Plugboard::random(seed, pairs).unwrap();
+
+        let mut moved = 0;
+        for x in 0..=255u8 {
+            let y = plugboard.forward(x, &state);
+            assert_eq!(plugboard.forward(y, &state), x);
+            if y != x {
+                moved += 1;
+            }
+        }
+        assert_eq!(moved, 2 * pairs);
+
+        assert!(plugboard.same_effect_as(&Plugboard::random(seed, pairs).unwrap()));
+    }
+
+    assert!(Plugboard::random(1, 129).is_err());
+}
+
+#[test]
+fn pairs_recover_the_swaps() {
+    let plugboard = Plugboard::from_pairs(&[(b'Z', b'A'), (0, 255), (7, 8)]).unwrap();
+    assert_eq!(plugboard.pairs(), vec![(0, 255), (7, 8), (b'A', b'Z')]);
+
+    assert!(Plugboard::identity().pairs().is_empty());
+
+    let random = Plugboard::random(9, 20).unwrap();
+    let rebuilt = Plugboard::from_pairs(&random.pairs()).unwrap();
+    assert_eq!(random.pairs().len(), 20);
+    assert!(rebuilt.same_effect_as(&random));
+}
+
+#[test]
+fn swapped_count_and_identity_detection() {
+    let identity = Plugboard::identity();
+    assert_eq!(identity.swapped_count(), 0);
+    assert!(identity.is_identity());
+
+    let three = Plugboard::from_pairs(&[(1, 2), (3, 4), (b'A', b'B')]).unwrap();
+    assert_eq!(three.swapped_count(), 6);
+    assert!(!three.is_identity());
+}
+
+#[test]
+fn asymmetric_mapping_error_names_the_index() {
+    let mut mapping = identity_mapping();
+    mapping[65] = 66;
+    mapping[66] = 67;
+
+    let err = Plugboard::new(mapping).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("plugboard not symmetric at 65: mapping[65]=66 but mapping[66]=67"),
+        "{err}"
+    );
+}
+
+#[test]
+fn rewire_changes_swaps() {
+    let mut plugboard = Plugboard::from_pairs(&[(b'A', b'B')]).unwrap();
+    let state = EnigmaState::new(1);
+    assert_eq!(plugboard.forward(b'A', &state), b'B');
+
+    plugboard.rewire(&[(b'A', b'C'), (b'X', b'Y')]).unwrap();
+
+    assert_eq!(plugboard.forward(b'A', &state), b'C');
+    assert_eq!(plugboard.forward(b'B', &state), b'B');
+    assert_eq!(plugboard.pairs(), vec![(b'A', b'C'), (b'X', b'Y')]);
+}
+
+#[test]
+fn failed_rewire_keeps_wiring() {
+    let mut plugboard = Plugboard::from_pairs(&[(b'A', b'B')]).unwrap();
+
+    assert!(plugboard.rewire(&[(b'A', b'C'), (b'A', b'D')]).is_err());
+    assert_eq!(plugboard.pairs(), vec![(b'A', b'B')]);
+}
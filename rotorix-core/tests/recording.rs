@@ -0,0 +1,52 @@
+use rotorix_core::recording::Direction;
+use rotorix_core::{
+    EnigmaComponent, EnigmaMachine, EnigmaState, LinearStepping, Plugboard, RecordingComponent,
+    Reflector, Rotor, SteppingStrategy,
+};
+
+fn machine(rotor: Box<dyn EnigmaComponent>) -> EnigmaMachine {
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::from_seed(0, 3)), rotor],
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn recording_matches_independent_computation() {
+    let recorder = RecordingComponent::new(Rotor::from_seed(1, 3));
+    let transcript = recorder.transcript();
+
+    let message = b"RECORD ME";
+    let recorded = machine(Box::new(recorder))
+        .process_bytes(message, &mut EnigmaState::new(2))
+        .unwrap();
+
+    // Recording must not change outputs
+    let plain = machine(Box::new(Rotor::from_seed(1, 3)))
+        .process_bytes(message, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_eq!(recorded, plain);
+
+    let entries = transcript.borrow();
+    assert_eq!(entries.len(), 2 * message.len());
+
+    let rotor = Rotor::from_seed(1, 3);
+    let mut state = EnigmaState::new(2);
+    let stepping = LinearStepping::try_new(256).unwrap();
+
+    for pair in entries.chunks(2) {
+        let (fwd, bwd) = (&pair[0], &pair[1]);
+
+        assert_eq!(fwd.direction, Direction::Forward);
+        assert_eq!(bwd.direction, Direction::Backward);
+        assert_eq!(fwd.positions, state.rotor_positions);
+        assert_eq!(bwd.positions, state.rotor_positions);
+        assert_eq!(fwd.output, rotor.forward(fwd.input, &state));
+        assert_eq!(bwd.output, rotor.backward(bwd.input, &state));
+
+        stepping.step(&mut state).unwrap();
+    }
+}
@@ -0,0 +1,205 @@
+use rotorix_core::{
+    EnigmaComponent, EnigmaError, EnigmaMachine, EnigmaState, LinearStepping, MovingReflector,
+    Plugboard, Reflector, Rotor, SwitchingReflector,
+};
+
+#[test]
+fn switching_reflector_selects_by_rotor_position() {
+    let reflector =
+        SwitchingReflector::new(vec![Reflector::paired(), Reflector::identity()]).unwrap();
+
+    let mut state = EnigmaState::new(1);
+    assert_eq!(reflector.forward(10, &state), 11);
+
+    state.rotor_positions[0] = 1;
+    assert_eq!(reflector.forward(10, &state), 10);
+
+    state.rotor_positions[0] = 2;
+    assert_eq!(reflector.forward(10, &state), 11);
+}
+
+#[test]
+fn switching_reflector_roundtrip() {
+    let reflector = SwitchingReflector::new(vec![
+        Reflector::paired(),
+        Reflector::paired(),
+        Reflector::identity(),
+    ])
+    .unwrap();
+
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::from_seed(0, 99))],
+        Box::new(reflector),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap();
+
+    let plaintext = b"SWITCHING REFLECTORS";
+    let ciphertext = machine
+        .process_bytes(plaintext, &mut EnigmaState::new(1))
+        .unwrap();
+    let decrypted = machine
+        .process_bytes(&ciphertext, &mut EnigmaState::new(1))
+        .unwrap();
+
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn switching_reflector_requires_candidates() {
+    assert!(SwitchingReflector::new(Vec::new()).is_err());
+}
+
+#[test]
+fn paired_reflector_has_same_effect_as_explicit_mapping() {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = (i as u8) ^ 1;
+    }
+
+    let explicit = Reflector::new(mapping).unwrap();
+
+    assert!(explicit.same_effect_as(&Reflector::paired()));
+    assert!(!explicit.same_effect_as(&Reflector::identity()));
+}
+
+#[test]
+fn reflector_from_pairs_swaps_listed_bytes() {
+    let reflector = Reflector::from_pairs(&[(b'A', b'Z'), (0, 255)]).unwrap();
+    let state = EnigmaState::new(1);
+
+    assert_eq!(reflector.forward(b'A', &state), b'Z');
+    assert_eq!(reflector.forward(b'Z', &state), b'A');
+    assert_eq!(reflector.forward(255, &state), 0);
+    assert_eq!(reflector.forward(b'M', &state), b'M');
+
+    assert!(
+        Reflector::from_pairs(&[])
+            .unwrap()
+            .same_effect_as(&Reflector::identity())
+    );
+}
+
+#[test]
+fn reflector_from_pairs_rejects_overlaps_and_self_pairs() {
+    let message = |pairs: &[(u8, u8)]| match Reflector::from_pairs(pairs) {
+        Err(EnigmaError::InvalidConfiguration(msg)) => msg,
+        other => panic!("unexpected result: {other:?}"),
+    };
+
+    assert!(message(&[(1, 2), (2, 3)]).contains("byte 2 appears in more than one pair"));
+    assert!(message(&[(1, 2), (2, 1)]).contains("more than one pair"));
+    assert!(message(&[(7, 7)]).contains("maps byte 7 to itself"));
+}
+
+#[test]
+fn random_reflector_is_a_fixed_point_free_involution() {
+    let state = EnigmaState::new(1);
+
+    for seed in [0, 1, 2, 99, u64::MAX] {
+        let reflector = Reflector::random(seed);
+
+        for x in 0..=255u8 {
+            let y = reflector.forward(x, &state);
+            assert_ne!(y, x);
+            assert_eq!(reflector.forward(y, &state), x);
+        }
+
+        assert!(reflector.same_effect_as(&Reflector::random(seed)));
+    }
+
+    assert!(!Reflector::random(1).same_effect_as(&Reflector::random(2)));
+}
+
+#[test]
+fn reflector_validate_names_first_asymmetric_byte() {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+    assert!(Reflector::validate(&mapping).is_ok());
+
+    // 3 -> 4, but 4 still maps to itself
+    mapping[3] = 4;
+    match Reflector::validate(&mapping) {
+        Err(EnigmaError::InvalidConfiguration(msg)) => {
+            assert!(msg.contains("3 -> 4 -> 4"), "{msg}")
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(Reflector::new(mapping).is_err());
+}
+
+#[test]
+fn reflector_fixed_points_are_listed() {
+    assert!(Reflector::paired().fixed_points().is_empty());
+    assert_eq!(Reflector::identity().fixed_points().len(), 256);
+
+    let reflector = Reflector::from_pairs(&[(0, 1)]).unwrap();
+    let fixed = reflector.fixed_points();
+    assert_eq!(fixed.len(), 254);
+    assert!(!fixed.contains(&0) && !fixed.contains(&1));
+}
+
+#[test]
+fn moving_reflector_rotates_but_stays_self_inverse() {
+    let base = Reflector::random(5);
+    let mut mapping = [0u8; 256];
+    let zero = EnigmaState::new(1);
+    for (x, v) in mapping.iter_mut().enumerate() {
+        *v = base.forward(x as u8, &zero);
+    }
+
+    let moving = MovingReflector::new(mapping, 256).unwrap();
+
+    let mut state = EnigmaState::new(1);
+    let mut changed = false;
+    for step in 0..300 {
+        state.step_counter = step;
+        for x in 0..=255u8 {
+            let y = moving.forward(x, &state);
+            assert_eq!(moving.forward(y, &state), x);
+        }
+        changed |= moving.forward(0, &state) != moving.forward(0, &zero);
+    }
+    assert!(changed);
+
+    // The machine round-trips when both sides replay the same states
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::from_seed(0, 5))],
+        Box::new(moving),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap();
+
+    let plaintext = b"MOVING REFLECTOR";
+    let ciphertext = machine
+        .process_bytes(plaintext, &mut EnigmaState::new(1))
+        .unwrap();
+    let decrypted = machine
+        .process_bytes(&ciphertext, &mut EnigmaState::new(1))
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn moving_reflector_validates_base_and_modulus() {
+    let mut mapping = [0u8; 256];
+    for (i, v) in mapping.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+
+    assert!(MovingReflector::new(mapping, 0).is_err());
+    assert!(MovingReflector::new(mapping, 257).is_err());
+
+    // 3 <-> 30 leaves the 26-symbol space
+    mapping[3] = 30;
+    mapping[30] = 3;
+    assert!(MovingReflector::new(mapping, 26).is_err());
+    assert!(MovingReflector::new(mapping, 64).is_ok());
+
+    mapping[30] = 30;
+    assert!(MovingReflector::new(mapping, 256).is_err());
+}
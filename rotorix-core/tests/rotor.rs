@@ -0,0 +1,451 @@
+use rotorix_core::{
+    EnigmaComponent, EnigmaError, EnigmaMachine, EnigmaState, LinearStepping, Plugboard, Reflector,
+    Rotor,
+};
+
+#[test]
+fn identity_rotor_has_only_fixed_points() {
+    let cycles = Rotor::identity(0).cycle_structure();
+
+    assert_eq!(cycles.len(), 256);
+    assert!(cycles.iter().all(|&len| len == 1));
+}
+
+#[test]
+fn shifted_rotor_is_a_single_cycle() {
+    // 13 is coprime with 256, so the shift visits every byte
+    assert_eq!(Rotor::shifted(0, 13).cycle_structure(), vec![256]);
+
+    // A shift of 128 pairs each byte with its opposite
+    let cycles = Rotor::shifted(0, 128).cycle_structure();
+    assert_eq!(cycles.len(), 128);
+    assert!(cycles.iter().all(|&len| len == 2));
+}
+
+#[test]
+fn cycle_lengths_cover_all_bytes() {
+    let cycles = Rotor::from_seed(0, 12345).cycle_structure();
+    assert_eq!(cycles.iter().sum::<usize>(), 256);
+}
+
+/// Maps `az09` text to symbol indices (a–z → 0–25, 0–9 → 26–35).
+fn to_symbols(text: &str) -> Vec<u8> {
+    text.bytes()
+        .map(|b| match b {
+            b'a'..=b'z' => b - b'a',
+            b'0'..=b'9' => b - b'0' + 26,
+            _ => panic!("not in az09"),
+        })
+        .collect()
+}
+
+fn symbol_wiring(seed: u8) -> [u8; 36] {
+    let mut wiring = [0u8; 36];
+    for (i, w) in wiring.iter_mut().enumerate() {
+        // 5 is coprime with 36, so this is a permutation
+        *w = ((i * 5 + 7 + seed as usize) % 36) as u8;
+    }
+    wiring
+}
+
+#[test]
+fn symbol_rotor_roundtrip_az09() {
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![
+            Box::new(Rotor::from_symbol_wiring(&symbol_wiring(0), 0).unwrap()),
+            Box::new(Rotor::from_symbol_wiring(&symbol_wiring(3), 1).unwrap()),
+        ],
+        // Pairs 0<->1, ..., 34<->35 stay inside the symbol space
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(36).unwrap()),
+    )
+    .unwrap();
+
+    let plaintext = to_symbols("attackatdawn1944");
+
+    let ciphertext = machine
+        .process_bytes(&plaintext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert!(ciphertext.iter().all(|&s| s < 36));
+    assert_ne!(ciphertext, plaintext);
+
+    let decrypted = machine
+        .process_bytes(&ciphertext, &mut EnigmaState::new(2))
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn symbol_wiring_must_be_a_permutation() {
+    let message = |wiring: &[u8; 36]| match Rotor::from_symbol_wiring(wiring, 0) {
+        Err(EnigmaError::InvalidConfiguration(msg)) => msg,
+        other => panic!("unexpected result: {other:?}"),
+    };
+
+    let mut wiring = symbol_wiring(0);
+    wiring[1] = wiring[0];
+    assert!(message(&wiring).contains("index 1 repeats output"));
+
+    let mut wiring = symbol_wiring(0);
+    wiring[0] = 36;
+    assert_eq!(
+        message(&wiring),
+        "rotor entry 36 at index 0 is outside the modulus 36"
+    );
+}
+
+#[test]
+fn notches_mark_turnover_positions() {
+    let rotor = Rotor::from_seed(1, 7).with_notches(vec![16, 200]);
+    assert_eq!(rotor.notches(), &[16, 200]);
+
+    let mut state = EnigmaState::new(2);
+    assert!(!rotor.is_at_notch(&state));
+
+    state.rotor_positions[1] = 16;
+    assert!(rotor.is_at_notch(&state));
+
+    // Other rotors' positions are irrelevant
+    state.rotor_positions = vec![200, 15];
+    assert!(!rotor.is_at_notch(&state));
+
+    // Out-of-bounds index is not at a notch
+    assert!(!rotor.is_at_notch(&EnigmaState::new(1)));
+
+    assert!(Rotor::identity(0).notches().is_empty());
+    assert!(Rotor::shifted(0, 3).notches().is_empty());
+}
+
+#[test]
+fn validate_names_first_duplicate() {
+    let mut perm = [0u8; 256];
+    for (i, v) in perm.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+    assert!(Rotor::validate(&perm, 256).is_ok());
+
+    perm[40] = 7;
+    perm[90] = 7;
+    match Rotor::validate(&perm, 256) {
+        Err(EnigmaError::InvalidConfiguration(msg)) => {
+            assert!(msg.contains("index 40 repeats output 7"), "{msg}")
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+
+    // new and new_with_modulus share the same check and message
+    for result in [Rotor::new(perm, 0), Rotor::new_with_modulus(perm, 0, 100)] {
+        match result {
+            Err(EnigmaError::InvalidConfiguration(msg)) => assert_eq!(
+                msg,
+                "rotor permutation must be bijective (index 40 repeats output 7)"
+            ),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    // Entries past the modulus are ignored, entries inside must stay below it
+    assert!(Rotor::validate(&perm, 30).is_ok());
+    assert!(Rotor::validate(&perm, 0).is_err());
+    perm[3] = 30;
+    assert!(Rotor::validate(&perm, 30).is_err());
+}
+
+#[test]
+fn inverse_swaps_directions_at_every_position() {
+    for seed in [0, 1, 42, 0xDEAD_BEEF] {
+        let rotor = Rotor::from_seed(0, seed);
+        let inverse = rotor.inverse();
+        let mut state = EnigmaState::new(1);
+
+        for pos in 0..256 {
+            state.rotor_positions[0] = pos;
+            for x in 0..=255u8 {
+                assert_eq!(rotor.forward(x, &state), inverse.backward(x, &state));
+                assert_eq!(rotor.backward(x, &state), inverse.forward(x, &state));
+            }
+        }
+    }
+}
+
+#[test]
+fn modulus_bounds_symbols_and_position_wrap() {
+    // Reverse the 26-letter alphabet
+    let mut perm = [0u8; 256];
+    for (i, v) in perm.iter_mut().enumerate().take(26) {
+        *v = 25 - i as u8;
+    }
+
+    let rotor = Rotor::new_with_modulus(perm, 0, 26).unwrap();
+    assert_eq!(rotor.modulus(), 26);
+    assert_eq!(Rotor::identity(0).modulus(), 256);
+    assert_eq!(Rotor::shifted(0, 1).modulus(), 256);
+
+    let mut state = EnigmaState::new(1);
+    for pos in 0..26 {
+        state.rotor_positions[0] = pos;
+        let wrapped = EnigmaState {
+            rotor_positions: vec![pos + 26],
+            step_counter: 0,
+        };
+
+        for x in 0..26u8 {
+            let y = rotor.forward(x, &state);
+            assert!(y < 26);
+            assert_eq!(rotor.forward(x, &wrapped), y);
+            assert_eq!(rotor.backward(y, &state), x);
+        }
+
+        // Outside the alphabet: pass through
+        assert_eq!(rotor.forward(b'a', &state), b'a');
+    }
+
+    // Entries must stay inside the modulus
+    perm[3] = 30;
+    assert!(Rotor::new_with_modulus(perm, 0, 26).is_err());
+    assert!(Rotor::new_with_modulus(perm, 0, 0).is_err());
+    assert!(Rotor::new_with_modulus(perm, 0, 257).is_err());
+}
+
+#[test]
+fn nearby_seeds_give_unrelated_wirings() {
+    let state = EnigmaState::new(2);
+
+    let differing = |a: &Rotor, b: &Rotor| {
+        (0..=255u8)
+            .filter(|&x| a.forward(x, &state) != b.forward(x, &state))
+            .count()
+    };
+
+    assert!(differing(&Rotor::from_seed(0, 1), &Rotor::from_seed(0, 2)) >= 128);
+
+    // Adjacent indices with the same seed are unrelated as well
+    assert!(differing(&Rotor::from_seed(0, 1), &Rotor::from_seed(1, 1)) >= 128);
+}
+
+#[test]
+fn wiring_str_roundtrip() {
+    let rotor = Rotor::from_seed(2, 99);
+    let wiring = rotor.to_wiring_str();
+
+    assert_eq!(wiring.len(), 512);
+    assert!(
+        wiring
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase())
+    );
+
+    let reloaded = Rotor::from_wiring_str(&format!("{wiring}\n"), 2).unwrap();
+    assert_eq!(reloaded.to_wiring_str(), wiring);
+
+    let state = EnigmaState::new(3);
+    for x in 0..=255u8 {
+        assert_eq!(reloaded.forward(x, &state), rotor.forward(x, &state));
+    }
+}
+
+#[test]
+fn wiring_str_errors_name_the_problem() {
+    let message = |s: &str| match Rotor::from_wiring_str(s, 0) {
+        Err(EnigmaError::InvalidConfiguration(msg)) => msg,
+        other => panic!("unexpected result: {other:?}"),
+    };
+
+    assert!(message("00FF").contains("got 4"));
+
+    let mut wiring = Rotor::identity(0).to_wiring_str();
+    wiring.replace_range(17..18, "G");
+    assert!(message(&wiring).contains("'G' at position 17"));
+
+    // Well-formed hex that is not a permutation
+    let zeros = "00".repeat(256);
+    assert!(message(&zeros).contains("bijective"));
+}
+
+#[test]
+fn composed_rotor_matches_sequence_at_position_zero() {
+    let first = Rotor::from_seed(0, 3);
+    let second = Rotor::from_seed(1, 3);
+    let composed = first.compose(&second, 0);
+
+    let state = EnigmaState::new(2);
+    for x in 0..=255u8 {
+        let sequential = second.forward(first.forward(x, &state), &state);
+        assert_eq!(composed.forward(x, &state), sequential);
+        assert_eq!(composed.backward(sequential, &state), x);
+    }
+}
+
+#[test]
+fn shifted_series_assigns_indices_and_shifts() {
+    let rotors = Rotor::shifted_series(3, &[1, 5, 200]).unwrap();
+    assert_eq!(rotors.len(), 3);
+
+    // Distinct positions make each rotor's index observable
+    let state = EnigmaState {
+        rotor_positions: vec![3, 7, 9],
+        step_counter: 0,
+    };
+    for (i, (rotor, shift)) in rotors.iter().zip([1u8, 5, 200]).enumerate() {
+        let expected = Rotor::shifted(i, shift);
+        for x in 0..=255u8 {
+            assert_eq!(rotor.forward(x, &state), expected.forward(x, &state));
+        }
+    }
+
+    assert!(Rotor::shifted_series(2, &[1, 2, 3]).is_err());
+}
+
+#[test]
+fn ring_setting_offsets_the_position() {
+    let plain = Rotor::from_seed(0, 21);
+    let ringed = Rotor::from_seed(0, 21).with_ring(1);
+
+    let at = |pos: u32| EnigmaState {
+        rotor_positions: vec![pos],
+        step_counter: 0,
+    };
+
+    for pos in 0..256 {
+        // ring 1 at `pos` behaves like ring 0 one position earlier
+        let earlier = at((pos + 255) % 256);
+        for x in 0..=255u8 {
+            assert_eq!(ringed.forward(x, &at(pos)), plain.forward(x, &earlier));
+            assert_eq!(ringed.backward(x, &at(pos)), plain.backward(x, &earlier));
+        }
+    }
+
+    // Ring 0 is the default
+    let unchanged = Rotor::from_seed(0, 21).with_ring(0);
+    assert_eq!(unchanged.forward(9, &at(4)), plain.forward(9, &at(4)));
+}
+
+#[test]
+fn position_symbol_reads_the_rotor_position() {
+    let state = EnigmaState {
+        rotor_positions: vec![5, 300],
+        step_counter: 0,
+    };
+
+    assert_eq!(Rotor::from_seed(0, 1).position_symbol(&state), 5);
+    // Wraps at the modulus
+    assert_eq!(
+        Rotor::from_seed(1, 1).try_position_symbol(&state).unwrap(),
+        44
+    );
+
+    let out_of_bounds = Rotor::from_seed(2, 1);
+    assert!(matches!(
+        out_of_bounds.try_position_symbol(&state),
+        Err(EnigmaError::InvalidState(_))
+    ));
+    assert_eq!(out_of_bounds.position_symbol(&state), 0);
+}
+
+#[test]
+fn fixed_points_are_counted() {
+    assert_eq!(Rotor::identity(0).fixed_point_count(), 256);
+    assert!(!Rotor::shifted(0, 1).has_fixed_points());
+
+    let state = EnigmaState::new(1);
+    for seed in [1, 2, 3, 1000, u64::MAX] {
+        let rotor = Rotor::from_seed(0, seed);
+        let expected = (0..=255u8)
+            .filter(|&x| rotor.forward(x, &state) == x)
+            .count();
+
+        assert_eq!(rotor.fixed_point_count(), expected);
+        assert_eq!(rotor.has_fixed_points(), expected > 0);
+        assert_eq!(
+            rotor
+                .cycle_structure()
+                .iter()
+                .filter(|&&len| len == 1)
+                .count(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn advance_moves_only_this_rotor() {
+    let rotor = Rotor::from_seed(1, 4);
+    let mut state = EnigmaState {
+        rotor_positions: vec![7, 254, 9],
+        step_counter: 3,
+    };
+
+    rotor.advance(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![7, 255, 9]);
+
+    rotor.advance(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![7, 0, 9]);
+    assert_eq!(state.step_counter, 3);
+
+    // Wraps at the rotor's own modulus
+    let mut perm = [0u8; 256];
+    for (i, v) in perm.iter_mut().enumerate() {
+        *v = i as u8;
+    }
+    let small = Rotor::new_with_modulus(perm, 0, 26).unwrap();
+    let mut state = EnigmaState {
+        rotor_positions: vec![25],
+        step_counter: 0,
+    };
+    small.advance(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![0]);
+
+    assert!(Rotor::identity(5).advance(&mut state).is_err());
+}
+
+#[test]
+fn advance_handles_maximum_position() {
+    let rotor = Rotor::identity(0);
+    let mut state = EnigmaState {
+        rotor_positions: vec![u32::MAX],
+        step_counter: 0,
+    };
+
+    rotor.advance(&mut state).unwrap();
+
+    // u32::MAX is 255 modulo 256
+    assert_eq!(state.rotor_positions, vec![0]);
+}
+
+#[test]
+fn label_names_the_rotor_in_debug_output() {
+    assert_eq!(Rotor::identity(0).label(), None);
+    assert_eq!(Rotor::shifted(0, 1).label(), None);
+    assert_eq!(Rotor::from_seed(0, 1).label(), None);
+
+    let rotor = Rotor::from_seed(0, 1).with_label("left");
+    assert_eq!(rotor.label(), Some("left"));
+
+    let debug = format!("{rotor:?}");
+    assert!(
+        debug.starts_with(r#"Rotor { label: Some("left"), index: 0"#),
+        "{debug}"
+    );
+    assert!(debug.len() < 200);
+}
+
+#[test]
+fn double_notch_rotor_reports_both_turnovers() {
+    // Historical VI–VIII turn over at Z and M
+    let rotor = Rotor::from_seed_with_notches(0, 6, vec![25, 12]);
+
+    assert_eq!(rotor.turnover_positions(), &[25, 12]);
+    assert_eq!(
+        rotor.to_wiring_str(),
+        Rotor::from_seed(0, 6).to_wiring_str()
+    );
+
+    let at = |pos: u32| EnigmaState {
+        rotor_positions: vec![pos],
+        step_counter: 0,
+    };
+    assert!(rotor.is_at_notch(&at(25)));
+    assert!(rotor.is_at_notch(&at(12)));
+    assert!(!rotor.is_at_notch(&at(13)));
+}
@@ -0,0 +1,235 @@
+use serde_json::{Value, json};
+
+use rotorix_core::{EnigmaComponent, EnigmaState, Plugboard, Reflector, Rotor};
+
+const ROTOR_JSON: &str = include_str!("fixtures/rotor.json");
+const REFLECTOR_JSON: &str = include_str!("fixtures/reflector.json");
+const PLUGBOARD_JSON: &str = include_str!("fixtures/plugboard.json");
+
+fn fixture(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+/// Returns `json` with `field` replaced by `value`.
+fn with_field(json: &str, field: &str, value: Value) -> String {
+    let mut fixture = fixture(json);
+    fixture[field] = value;
+    fixture.to_string()
+}
+
+#[test]
+fn rotor_fixture_roundtrips() {
+    let rotor: Rotor = serde_json::from_str(ROTOR_JSON).unwrap();
+
+    assert_eq!(rotor.to_wiring_str(), fixture(ROTOR_JSON)["forward"]);
+    assert_eq!(rotor.notches(), [16, 200]);
+    assert_eq!(serde_json::to_value(&rotor).unwrap(), fixture(ROTOR_JSON));
+}
+
+#[test]
+fn rotor_roundtrip_preserves_wiring_and_settings() {
+    let rotor = Rotor::from_seed(1, 77)
+        .with_ring(3)
+        .with_notches(vec![16, 200])
+        .with_label("VII");
+
+    let json = serde_json::to_string(&rotor).unwrap();
+    assert!(json.contains(r#""label":"VII""#), "{json}");
+    let restored: Rotor = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(restored.to_wiring_str(), rotor.to_wiring_str());
+    assert_eq!(restored.notches(), rotor.notches());
+    assert_eq!(restored.label(), Some("VII"));
+
+    let state = EnigmaState {
+        rotor_positions: vec![0, 42],
+        step_counter: 0,
+    };
+    for x in 0..=255u8 {
+        assert_eq!(restored.forward(x, &state), rotor.forward(x, &state));
+        assert_eq!(restored.backward(x, &state), rotor.backward(x, &state));
+    }
+}
+
+#[test]
+fn rotor_settings_default_when_omitted() {
+    let json = json!({
+        "forward": fixture(ROTOR_JSON)["forward"],
+        "index": 0,
+    });
+
+    let rotor: Rotor = serde_json::from_value(json).unwrap();
+
+    assert_eq!(rotor.modulus(), 256);
+    assert!(rotor.notches().is_empty());
+    assert_eq!(rotor.label(), None);
+}
+
+#[test]
+fn invalid_rotor_json_is_rejected() {
+    let json = with_field(ROTOR_JSON, "forward", json!("00".repeat(256)));
+    let err = serde_json::from_str::<Rotor>(&json).unwrap_err();
+    assert!(
+        err.to_string().contains("index 1 repeats output 0"),
+        "{err}"
+    );
+
+    let json = with_field(ROTOR_JSON, "forward", json!("0G"));
+    let err = serde_json::from_str::<Rotor>(&json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("rotor wiring must be 512 hex digits"),
+        "{err}"
+    );
+
+    let json = with_field(ROTOR_JSON, "extra", json!(1));
+    let err = serde_json::from_str::<Rotor>(&json).unwrap_err();
+    assert!(err.to_string().contains("unknown field `extra`"), "{err}");
+}
+
+#[test]
+fn reflector_and_plugboard_fixtures_roundtrip() {
+    let reflector: Reflector = serde_json::from_str(REFLECTOR_JSON).unwrap();
+    let state = EnigmaState::new(1);
+    assert_eq!(reflector.forward(0, &state), 1);
+    assert_eq!(reflector.forward(255, &state), 254);
+    assert_eq!(
+        serde_json::to_value(&reflector).unwrap(),
+        fixture(REFLECTOR_JSON)
+    );
+
+    let plugboard: Plugboard = serde_json::from_str(PLUGBOARD_JSON).unwrap();
+    assert_eq!(plugboard.pairs(), vec![(b'A', b'Q')]);
+    assert_eq!(
+        serde_json::to_value(&plugboard).unwrap(),
+        fixture(PLUGBOARD_JSON)
+    );
+
+    let random = Reflector::random(12);
+    let json = serde_json::to_string(&random).unwrap();
+    let restored: Reflector = serde_json::from_str(&json).unwrap();
+    assert!(restored.same_effect_as(&random));
+}
+
+#[test]
+fn non_involutive_mappings_are_rejected_on_deserialize() {
+    // 0 -> 1 -> 2: a rotation, not an involution
+    let rotation: String = (0..=255u8)
+        .map(|i| format!("{:02X}", i.wrapping_add(1)))
+        .collect();
+    let json = json!({ "mapping": rotation }).to_string();
+
+    let err = serde_json::from_str::<Reflector>(&json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("reflector mapping must be symmetric"),
+        "{err}"
+    );
+
+    let err = serde_json::from_str::<Plugboard>(&json).unwrap_err();
+    assert!(
+        err.to_string().contains("plugboard not symmetric at 0"),
+        "{err}"
+    );
+
+    let short = json!({ "mapping": "0001" }).to_string();
+    let err = serde_json::from_str::<Plugboard>(&short).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("plugboard mapping must be 512 hex digits"),
+        "{err}"
+    );
+}
+
+fn sample_state() -> EnigmaState {
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions = vec![7, 255, 4_000_000_000];
+    state.step_counter = u64::MAX - 1;
+    state
+}
+
+#[test]
+fn state_roundtrips_through_serde_json() {
+    let state = sample_state();
+
+    let value = serde_json::to_value(&state).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "rotor_positions": [7, 255, 4_000_000_000u32],
+            "step_counter": u64::MAX - 1,
+        })
+    );
+    assert_eq!(serde_json::from_value::<EnigmaState>(value).unwrap(), state);
+}
+
+#[test]
+fn state_roundtrips_through_json() {
+    let state = sample_state();
+
+    let json = state.to_json();
+    assert_eq!(
+        json,
+        r#"{"rotor_positions":[7,255,4000000000],"step_counter":18446744073709551614}"#
+    );
+    assert_eq!(EnigmaState::from_json(&json).unwrap(), state);
+
+    let spaced = "{ \"step_counter\" : 3 ,\n \"rotor_positions\" : [ ] }";
+    assert_eq!(
+        EnigmaState::from_json(spaced).unwrap(),
+        EnigmaState {
+            rotor_positions: vec![],
+            step_counter: 3,
+        }
+    );
+}
+
+#[test]
+fn malformed_state_json_is_rejected() {
+    for (json, expected) in [
+        (r#"{"rotor_positions":[1]}"#, "missing field `step_counter`"),
+        (
+            r#"{"rotor_positions":[1],"step_counter":1,"extra":2}"#,
+            "unknown field `extra`",
+        ),
+        (
+            r#"{"rotor_positions":[4294967296],"step_counter":1}"#,
+            "invalid value: integer `4294967296`",
+        ),
+        (
+            r#"{"rotor_positions":[1],"step_counter":1} x"#,
+            "trailing characters",
+        ),
+        (
+            r#"{"rotor_positions":[1] "step_counter":1}"#,
+            "expected `,` or `}`",
+        ),
+    ] {
+        let err = EnigmaState::from_json(json).unwrap_err();
+        assert!(err.contains(expected), "{json}: {err}");
+    }
+}
+
+#[test]
+fn state_save_and_load_roundtrip() {
+    let dir = std::env::temp_dir().join(format!("rotorix-state-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("session.json");
+
+    let state = sample_state();
+    state.save(&path).unwrap();
+    assert_eq!(EnigmaState::load(&path).unwrap(), state);
+
+    // Saving again replaces the file and leaves no temporary behind
+    let mut next = state.clone();
+    next.step_counter = 1;
+    next.save(&path).unwrap();
+    assert_eq!(EnigmaState::load(&path).unwrap(), next);
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::write(&path, "not json").unwrap();
+    let err = EnigmaState::load(&path).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
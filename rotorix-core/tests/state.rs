@@ -0,0 +1,123 @@
+use rotorix_core::{
+    EnigmaMachine, EnigmaState, LinearStepping, NotchStepping, Plugboard, Reflector, Rotor,
+};
+
+fn machine() -> EnigmaMachine {
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![
+            Box::new(Rotor::from_seed(0, 11)),
+            Box::new(Rotor::from_seed(1, 11)),
+        ],
+        Box::new(Reflector::random(11)),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn restore_rolls_back_processing() {
+    let machine = machine();
+    let mut state = EnigmaState::new(2);
+    machine.process_bytes(b"PREFIX", &mut state).unwrap();
+
+    let snapshot = state.snapshot();
+    let first = machine.process_bytes(b"attempt", &mut state).unwrap();
+    assert_ne!(state, snapshot);
+
+    state.restore(&snapshot);
+    assert_eq!(state, snapshot);
+
+    // Retrying from the restored state reproduces the same output
+    assert_eq!(
+        machine.process_bytes(b"attempt", &mut state).unwrap(),
+        first
+    );
+}
+
+#[test]
+fn set_positions_assigns_valid_positions() {
+    let mut state = EnigmaState::new(3);
+
+    state.set_positions(&[0, 13, 25], 26).unwrap();
+
+    assert_eq!(state.rotor_positions, vec![0, 13, 25]);
+}
+
+#[test]
+fn set_positions_rejects_length_mismatch() {
+    let mut state = EnigmaState::new(3);
+
+    let err = state.set_positions(&[1, 2], 26).unwrap_err();
+
+    assert!(err.contains("expected 3 position(s), got 2"), "{err}");
+    assert_eq!(state, EnigmaState::new(3));
+}
+
+#[test]
+fn set_positions_rejects_out_of_range_values() {
+    let mut state = EnigmaState::new(3);
+
+    let err = state.set_positions(&[1, 26, 2], 26).unwrap_err();
+
+    assert!(err.contains("position 26 of rotor 1"), "{err}");
+    assert_eq!(state, EnigmaState::new(3));
+}
+
+#[test]
+fn from_seed_is_deterministic_and_in_range() {
+    let a = EnigmaState::from_seed(10, 0xDEAD_BEEF_0123_4567, 26);
+    let b = EnigmaState::from_seed(10, 0xDEAD_BEEF_0123_4567, 26);
+
+    assert_eq!(a, b);
+    assert_eq!(a.step_counter, 0);
+    assert!(a.rotor_positions.iter().all(|&p| p < 26));
+
+    // Byte 0 is 0x67 = 103 = 3 * 26 + 25; rotors past the eighth are 0
+    assert_eq!(a.rotor_positions[0], 25);
+    assert_eq!(a.rotor_positions[8..], [0, 0]);
+    assert_ne!(a, EnigmaState::from_seed(10, 0xDEAD_BEEF_0123_4568, 26));
+}
+
+#[test]
+fn from_seed_with_byte_modulus_uses_raw_bytes() {
+    let state = EnigmaState::from_seed(3, 0x0003_0201, 256);
+
+    assert_eq!(state.rotor_positions, vec![1, 2, 3]);
+}
+
+#[test]
+fn display_shows_positions_and_step() {
+    let state = EnigmaState {
+        rotor_positions: vec![0, 13, 5],
+        step_counter: 42,
+    };
+
+    assert_eq!(state.to_string(), "positions=[0, 13, 5] step=42");
+    assert_eq!(EnigmaState::new(0).to_string(), "positions=[] step=0");
+}
+
+#[test]
+fn rewind_undoes_advance() {
+    let stepping = LinearStepping::try_new(26).unwrap();
+    let mut state = EnigmaState::from_seed(3, 0x0019_0019, 26);
+    let original = state.clone();
+
+    state.advance_by(&stepping, 1000).unwrap();
+    assert_ne!(state, original);
+
+    state.rewind(&stepping, 1000).unwrap();
+    assert_eq!(state, original);
+}
+
+#[test]
+fn rewind_requires_reverse_stepping() {
+    let stepping = NotchStepping::new(26, vec![vec![16]]);
+    let mut state = EnigmaState::new(1);
+    state.advance_by(&stepping, 3).unwrap();
+
+    let err = state.rewind(&stepping, 1).unwrap_err();
+
+    assert_eq!(err, "reverse stepping not supported");
+    assert_eq!(state.step_counter, 3);
+}
@@ -0,0 +1,494 @@
+use rotorix_core::{
+    AlphabetStepping, ChainedStepping, DoubleStepping, EnigmaMachine, EnigmaState, GappedStepping,
+    LinearStepping, NotchStepping, Plugboard, RandomStepping, Reflector, Rotor, StepReport,
+    SteppingStrategy,
+};
+
+#[test]
+fn inactive_rotor_keeps_its_position() {
+    let stepping = LinearStepping::try_new(3)
+        .unwrap()
+        .with_active(vec![true, true, false]);
+
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions[2] = 1;
+
+    for _ in 0..9 {
+        stepping.step(&mut state).unwrap();
+        assert_eq!(state.rotor_positions[2], 1);
+    }
+
+    // Rotors 0 and 1 behave as a two-digit odometer in base 3;
+    // the carry out of rotor 1 is discarded instead of moving rotor 2.
+    assert_eq!(state.rotor_positions, vec![0, 0, 1]);
+    assert_eq!(state.step_counter, 9);
+}
+
+#[test]
+fn carry_skips_inactive_rotor() {
+    let stepping = LinearStepping::try_new(2)
+        .unwrap()
+        .with_active(vec![true, false, true]);
+
+    let mut state = EnigmaState::new(3);
+
+    stepping.step(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![1, 0, 0]);
+
+    stepping.step(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![0, 0, 1]);
+}
+
+#[test]
+fn zero_modulus_is_rejected_up_front() {
+    assert!(LinearStepping::try_new(0).is_err());
+    assert_eq!(LinearStepping::try_new(26).unwrap().modulus, 26);
+}
+
+fn two_rotor_machine(modulus: u32) -> EnigmaMachine {
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![
+            Box::new(Rotor::from_seed(0, 5)),
+            Box::new(Rotor::from_seed(1, 5)),
+        ],
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(modulus).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn short_period_is_flagged_weak() {
+    let start = EnigmaState::new(2);
+
+    let weak = two_rotor_machine(2);
+    assert_eq!(weak.period(&start, 100).unwrap(), Some(4));
+    assert!(weak.is_weak_period(&start, 100).unwrap());
+    assert!(!weak.is_weak_period(&start, 4).unwrap());
+
+    let strong = two_rotor_machine(256);
+    assert_eq!(strong.period(&start, 100).unwrap(), None);
+    assert!(!strong.is_weak_period(&start, 100).unwrap());
+}
+
+#[test]
+fn notch_stepping_carries_only_off_a_notch() {
+    // Rotor 0 has its notch at Q (16), rotor 1 at E (4)
+    let stepping = NotchStepping::new(26, vec![vec![16], vec![4], vec![]]);
+
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions = vec![15, 3, 0];
+
+    let mut seen = Vec::new();
+    for _ in 0..3 {
+        stepping.step(&mut state).unwrap();
+        seen.push(state.rotor_positions.clone());
+    }
+
+    assert_eq!(seen, vec![vec![16, 3, 0], vec![17, 4, 0], vec![18, 4, 0]]);
+
+    // A carry into rotor 1 while it sits on its notch moves rotor 2 too
+    state.rotor_positions = vec![16, 4, 0];
+    stepping.step(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![17, 5, 1]);
+    assert_eq!(state.step_counter, 4);
+}
+
+#[test]
+fn notch_stepping_does_not_carry_on_wrap() {
+    let stepping = NotchStepping::new(26, vec![vec![16]]);
+
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![25, 0];
+    stepping.step(&mut state).unwrap();
+
+    assert_eq!(state.rotor_positions, vec![0, 0]);
+}
+
+#[test]
+fn notch_stepping_takes_notches_from_rotors() {
+    let rotors = [
+        Rotor::from_seed_with_notches(0, 1, vec![2]),
+        Rotor::from_seed(1, 1),
+    ];
+    let stepping = NotchStepping::from_rotors(26, &rotors);
+
+    assert_eq!(stepping.notches, vec![vec![2], vec![]]);
+}
+
+/// Renders positions `[fast, middle, slow]` as the window letters,
+/// left rotor first.
+fn window(state: &EnigmaState) -> String {
+    state
+        .rotor_positions
+        .iter()
+        .rev()
+        .map(|&p| (b'A' + p as u8) as char)
+        .collect()
+}
+
+#[test]
+fn double_stepping_matches_historical_sequence() {
+    // Rotors I-II-III: III (fast) turns over at V, II (middle) at E
+    let stepping = DoubleStepping::new(26, vec![vec![21], vec![4], vec![16]]);
+
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions = vec![20, 3, 0]; // ADU
+
+    let expected = [
+        "ADV", "AEW", "BFX", "BFY", "BFZ", "BFA", "BFB", "BFC", "BFD", "BFE", "BFF", "BFG", "BFH",
+        "BFI", "BFJ", "BFK", "BFL", "BFM", "BFN", "BFO", "BFP", "BFQ", "BFR", "BFS", "BFT", "BFU",
+        "BFV", "BGW", "BGX", "BGY",
+    ];
+
+    for (i, expected) in expected.iter().enumerate() {
+        stepping.step(&mut state).unwrap();
+        assert_eq!(window(&state), *expected, "after step {}", i + 1);
+    }
+
+    assert_eq!(state.step_counter, 30);
+}
+
+#[test]
+fn gapped_stepping_holds_positions_between_advances() {
+    let stepping = GappedStepping::new(26, 4);
+    let mut state = EnigmaState::new(2);
+
+    for i in 1..4 {
+        stepping.step(&mut state).unwrap();
+        assert_eq!(state.rotor_positions, vec![0, 0]);
+        assert_eq!(state.step_counter, i);
+    }
+
+    stepping.step(&mut state).unwrap();
+    assert_eq!(state.rotor_positions, vec![1, 0]);
+    assert_eq!(state.step_counter, 4);
+}
+
+#[test]
+fn gapped_stepping_rejects_zero_gap() {
+    let mut state = EnigmaState::new(1);
+
+    assert!(GappedStepping::new(26, 0).step(&mut state).is_err());
+    assert_eq!(state.step_counter, 0);
+}
+
+#[test]
+fn per_rotor_moduli_form_mixed_radix_odometer() {
+    let stepping = LinearStepping::with_moduli(vec![26, 10, 2]);
+    assert_eq!(stepping.moduli(), [26, 10, 2]);
+    let mut state = EnigmaState::new(3);
+
+    for _ in 0..26 {
+        stepping.step(&mut state).unwrap();
+    }
+    assert_eq!(state.rotor_positions, vec![0, 1, 0]);
+
+    // 26 * 10 steps roll the middle rotor over into the last one
+    for _ in 0..26 * 9 {
+        stepping.step(&mut state).unwrap();
+    }
+    assert_eq!(state.rotor_positions, vec![0, 0, 1]);
+
+    // And 26 * 10 * 2 steps bring the whole odometer back to zero
+    for _ in 0..26 * 10 {
+        stepping.step(&mut state).unwrap();
+    }
+    assert_eq!(state.rotor_positions, vec![0, 0, 0]);
+
+    assert_eq!(stepping.modulus(), None);
+    assert_eq!(stepping.steps_until_advance(&state, 2), Some(260));
+}
+
+#[test]
+fn per_rotor_moduli_must_match_rotor_count() {
+    let stepping = LinearStepping::with_moduli(vec![26, 10]);
+    let mut state = EnigmaState::new(3);
+
+    let err = stepping.step(&mut state).unwrap_err();
+    assert!(err.contains("2 moduli"), "{err}");
+    assert_eq!(state.step_counter, 0);
+}
+
+#[test]
+fn active_flags_must_match_rotor_count() {
+    for active in [vec![true, false], vec![true, false, true, true]] {
+        let stepping = LinearStepping::try_new(26).unwrap().with_active(active);
+        let mut state = EnigmaState::new(3);
+
+        let err = stepping.step(&mut state).unwrap_err();
+        assert!(
+            err.contains("active flag(s) but the state has 3 rotor(s)"),
+            "{err}"
+        );
+        assert_eq!(state, EnigmaState::new(3));
+        assert!(stepping.validate(3).is_err());
+    }
+}
+
+#[test]
+fn step_back_undoes_step_from_random_states() {
+    let stepping = LinearStepping::try_new(5)
+        .unwrap()
+        .with_active(vec![true, false, true, true]);
+
+    // Small LCG so the start states are varied but reproducible
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |bound: u32| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        ((seed >> 33) % u64::from(bound)) as u32
+    };
+
+    for _ in 0..500 {
+        let mut state = EnigmaState::new(4);
+        for pos in &mut state.rotor_positions {
+            *pos = next(5);
+        }
+        state.step_counter = u64::from(next(1000));
+
+        let original = state.clone();
+        stepping.step(&mut state).unwrap();
+        stepping.step_back(&mut state).unwrap();
+
+        assert_eq!(state, original);
+    }
+}
+
+#[test]
+fn step_back_borrows_across_rotors() {
+    let stepping = LinearStepping::with_moduli(vec![26, 10]);
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![0, 3];
+    state.step_counter = 1;
+
+    stepping.step_back(&mut state).unwrap();
+
+    assert_eq!(state.rotor_positions, vec![25, 2]);
+    assert_eq!(state.step_counter, 0);
+    assert!(stepping.step_back(&mut state).is_err());
+}
+
+#[test]
+fn chained_stepping_runs_each_strategy() {
+    let stepping = ChainedStepping(vec![
+        Box::new(LinearStepping::try_new(26).unwrap()),
+        Box::new(LinearStepping::try_new(26).unwrap()),
+    ]);
+    let mut state = EnigmaState::new(2);
+
+    for i in 1..=13 {
+        stepping.step(&mut state).unwrap();
+        assert_eq!(state.rotor_positions[0], 2 * i % 26);
+    }
+
+    assert_eq!(state.rotor_positions, vec![0, 1]);
+    assert_eq!(state.step_counter, 26);
+}
+
+#[test]
+#[allow(deprecated)]
+fn chained_stepping_stops_at_first_error() {
+    let stepping = ChainedStepping(vec![
+        Box::new(LinearStepping::new(0)),
+        Box::new(LinearStepping::try_new(26).unwrap()),
+    ]);
+    let mut state = EnigmaState::new(1);
+
+    assert!(stepping.step(&mut state).is_err());
+    assert_eq!(state, EnigmaState::new(1));
+}
+
+#[test]
+fn linear_step_report_lists_carries() {
+    let stepping = LinearStepping::try_new(3)
+        .unwrap()
+        .with_active(vec![true, false, true]);
+    let mut state = EnigmaState::new(3);
+    state.rotor_positions = vec![1, 0, 0];
+
+    let report = stepping.step_detailed(&mut state).unwrap();
+    assert_eq!(
+        report,
+        StepReport {
+            advanced: vec![0],
+            carry: false,
+        }
+    );
+
+    let report = stepping.step_detailed(&mut state).unwrap();
+    assert_eq!(
+        report,
+        StepReport {
+            advanced: vec![0, 2],
+            carry: true,
+        }
+    );
+    assert_eq!(state.rotor_positions, vec![0, 0, 1]);
+    assert_eq!(state.step_counter, 2);
+}
+
+#[test]
+fn default_step_report_compares_positions() {
+    let stepping = NotchStepping::new(26, vec![vec![16]]);
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![16, 0];
+
+    let report = stepping.step_detailed(&mut state).unwrap();
+
+    assert_eq!(report.advanced, vec![0, 1]);
+    assert!(report.carry);
+}
+
+#[test]
+fn random_stepping_roundtrips_through_machine() {
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::from_pairs(&[(b'A', b'Z')]).unwrap()),
+        vec![
+            Box::new(Rotor::from_seed(0, 5)),
+            Box::new(Rotor::from_seed(1, 5)),
+        ],
+        Box::new(Reflector::random(9)),
+        Box::new(RandomStepping::new(256, 1234)),
+    )
+    .unwrap();
+
+    let plaintext = b"ATTACK AT DAWN, ATTACK AT DAWN";
+    let ciphertext = machine
+        .process_bytes(plaintext, &mut EnigmaState::new(2))
+        .unwrap();
+    let decrypted = machine
+        .process_bytes(&ciphertext, &mut EnigmaState::new(2))
+        .unwrap();
+
+    assert_ne!(ciphertext, plaintext);
+    assert_eq!(decrypted, plaintext);
+}
+
+#[test]
+fn random_stepping_is_reproducible_and_never_stalls() {
+    let a = RandomStepping::new(26, 42);
+    let b = RandomStepping::new(26, 42);
+    let mut sa = EnigmaState::new(2);
+    let mut sb = EnigmaState::new(2);
+
+    for _ in 0..100 {
+        let before = sa.rotor_positions[0];
+        a.step(&mut sa).unwrap();
+        b.step(&mut sb).unwrap();
+
+        assert_eq!(sa, sb);
+        assert_ne!(sa.rotor_positions[0], before);
+        assert!(sa.rotor_positions.iter().all(|&p| p < 26));
+    }
+
+    assert!(RandomStepping::new(1, 42).step(&mut sa).is_err());
+}
+
+#[test]
+fn alphabet_stepping_skips_non_letters() {
+    let stepping = AlphabetStepping::new();
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![24, 0];
+
+    for &byte in b"a Z.9" {
+        stepping.step_with_input(&mut state, byte).unwrap();
+    }
+
+    // Only `a` and `Z` moved the rotors, wrapping at 26
+    assert_eq!(state.rotor_positions, vec![0, 1]);
+    assert_eq!(state.step_counter, 5);
+}
+
+#[test]
+fn machine_passes_input_to_stepping() {
+    let machine = EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![Box::new(Rotor::identity(0))],
+        Box::new(Reflector::identity()),
+        Box::new(AlphabetStepping::new()),
+    )
+    .unwrap();
+
+    let mut state = EnigmaState::new(1);
+    machine.process_bytes(b"HI THERE  !", &mut state).unwrap();
+
+    assert_eq!(state.rotor_positions, vec![7]);
+    assert_eq!(state.step_counter, 11);
+}
+
+#[test]
+fn advance_by_equals_repeated_steps() {
+    let stepping = LinearStepping::try_new(3).unwrap();
+
+    let mut advanced = EnigmaState::new(2);
+    advanced.advance_by(&stepping, 5).unwrap();
+
+    let mut stepped = EnigmaState::new(2);
+    for _ in 0..5 {
+        stepping.step(&mut stepped).unwrap();
+    }
+
+    assert_eq!(advanced, stepped);
+    assert_eq!(advanced.rotor_positions, vec![2, 1]);
+    assert!(EnigmaState::new(0).advance_by(&stepping, 1).is_err());
+}
+
+#[test]
+fn carry_on_step_predicts_first_rotor_wrap() {
+    let mut state = EnigmaState::new(2);
+
+    for (pos, carry) in [(0, false), (24, false), (25, true)] {
+        state.rotor_positions[0] = pos;
+        assert_eq!(LinearStepping::carry_on_step(&state, 26), carry, "at {pos}");
+    }
+
+    // The prediction matches what the step does
+    LinearStepping::try_new(26)
+        .unwrap()
+        .step(&mut state)
+        .unwrap();
+    assert_eq!(state.rotor_positions, vec![0, 1]);
+
+    state.rotor_positions = vec![0, 0];
+    assert!(LinearStepping::carry_on_step(&state, 1));
+    assert!(!LinearStepping::carry_on_step(&state, 0));
+    assert!(!LinearStepping::carry_on_step(&EnigmaState::new(0), 26));
+}
+
+#[test]
+fn gapped_stepping_rejects_empty_state_on_every_call() {
+    let stepping = GappedStepping::new(26, 3);
+    let mut state = EnigmaState::new(0);
+
+    for _ in 0..3 {
+        assert!(stepping.step(&mut state).is_err());
+    }
+    assert_eq!(state.step_counter, 0);
+}
+
+#[test]
+fn notch_stepping_handles_maximum_position() {
+    let stepping = NotchStepping::new(26, vec![vec![16]]);
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![u32::MAX, 0];
+
+    stepping.step(&mut state).unwrap();
+
+    // u32::MAX is 21 modulo 26
+    assert_eq!(state.rotor_positions, vec![22, 0]);
+}
+
+#[test]
+fn double_stepping_handles_maximum_position() {
+    let stepping = DoubleStepping::new(26, vec![vec![u32::MAX], vec![]]);
+    let mut state = EnigmaState::new(2);
+    state.rotor_positions = vec![u32::MAX, u32::MAX];
+
+    stepping.step(&mut state).unwrap();
+
+    // The pawl engages, so both rotors move; u32::MAX is 21 modulo 26
+    assert_eq!(state.rotor_positions, vec![22, 22]);
+}
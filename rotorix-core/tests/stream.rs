@@ -0,0 +1,53 @@
+use rotorix_core::{
+    EnigmaMachine, EnigmaState, LinearStepping, Plugboard, Reflector, Rotor, StateLengthPolicy,
+    StreamCipher,
+};
+
+fn machine() -> EnigmaMachine {
+    EnigmaMachine::new(
+        Box::new(Plugboard::identity()),
+        vec![
+            Box::new(Rotor::from_seed(0, 9)),
+            Box::new(Rotor::from_seed(1, 9)),
+        ],
+        Box::new(Reflector::paired()),
+        Box::new(LinearStepping::try_new(256).unwrap()),
+    )
+    .unwrap()
+}
+
+#[test]
+fn chunk_boundaries_do_not_affect_output() {
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+
+    let mut whole = StreamCipher::new(machine(), EnigmaState::new(2)).unwrap();
+    let expected = whole.update(&data).unwrap();
+
+    let mut chunked = StreamCipher::new(machine(), EnigmaState::new(2)).unwrap();
+    let mut output = Vec::new();
+    for (i, chunk) in data.chunks(13).enumerate() {
+        output.extend(chunked.update(chunk).unwrap());
+        // Empty chunks are harmless
+        if i % 5 == 0 {
+            assert!(chunked.update(&[]).unwrap().is_empty());
+        }
+    }
+
+    assert_eq!(output, expected);
+    assert_eq!(chunked.state(), whole.state());
+    assert_eq!(chunked.state().step_counter, 1000);
+}
+
+#[test]
+fn mismatched_state_is_rejected() {
+    assert!(StreamCipher::new(machine(), EnigmaState::new(3)).is_err());
+}
+
+#[test]
+fn truncating_policy_trims_the_state_up_front() {
+    let machine = machine().with_state_policy(StateLengthPolicy::Truncate);
+    let stream = StreamCipher::new(machine, EnigmaState::new(3)).unwrap();
+
+    assert_eq!(stream.state().rotor_positions, vec![0, 0]);
+    assert_eq!(stream.state().step_counter, 0);
+}
@@ -0,0 +1,18 @@
+use rotorix_core::test_support::deterministic_bytes;
+
+#[test]
+fn deterministic_bytes_are_reproducible_and_cover_all_values() {
+    let a = deterministic_bytes(42, 65_536);
+    assert_eq!(a.len(), 65_536);
+    assert_eq!(a, deterministic_bytes(42, 65_536));
+    assert_ne!(a, deterministic_bytes(43, 65_536));
+
+    // Shorter requests are a prefix of longer ones
+    assert_eq!(deterministic_bytes(42, 13), a[..13]);
+
+    let mut seen = [false; 256];
+    for &b in &a {
+        seen[b as usize] = true;
+    }
+    assert!(seen.iter().all(|&s| s));
+}